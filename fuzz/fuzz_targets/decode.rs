@@ -0,0 +1,38 @@
+//! Decode arbitrary bytes through every entry point; none may panic, and
+//! whatever decodes must describe whole elements.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use minicbor_typed_arrays::{
+    locate, AlignedTypedArray, ElementReader, IoSource, OwnedMultiDimArray, OwnedTypedArray,
+    TypedArrayRef,
+};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(a) = minicbor::decode::<TypedArrayRef>(data) {
+        assert_eq!(a.as_bytes().len(), a.len() * a.element_type().width());
+        assert_eq!(a.iter().count(), a.len());
+        let owned: OwnedTypedArray = minicbor::decode(data).unwrap();
+        let aligned: AlignedTypedArray = minicbor::decode(data).unwrap();
+        assert_eq!(owned.as_bytes(), a.as_bytes());
+        assert_eq!(aligned.as_bytes(), a.as_bytes());
+    }
+    let _ = minicbor::decode::<OwnedMultiDimArray>(data);
+
+    // `read_to_end` preallocates at most a bounded amount, so a huge declared
+    // length cannot exhaust memory before the short input runs out.
+    if let Ok(reader) = ElementReader::new(IoSource(data)) {
+        let _ = reader.read_to_end();
+    }
+
+    if let Ok(found) = locate(data) {
+        for location in found {
+            // `locate` skips payloads that are not whole elements, so every
+            // range it reports must decode.
+            let a: TypedArrayRef = minicbor::decode(&data[location.offset..location.end])
+                .expect("located range decodes");
+            assert_eq!(a.element_type(), location.element_type);
+            assert_eq!(a.len(), location.len);
+        }
+    }
+});
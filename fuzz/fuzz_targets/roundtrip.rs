@@ -0,0 +1,24 @@
+//! Encode an arbitrary typed array and check that every decoder gives it back.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use minicbor_typed_arrays::{locate, ElementReader, IoSource, OwnedTypedArray, TypedArrayRef};
+
+fuzz_target!(|array: OwnedTypedArray| {
+    let buf = minicbor::to_vec(&array).unwrap();
+
+    assert_eq!(minicbor::decode::<OwnedTypedArray>(&buf).unwrap(), array);
+    let borrowed: TypedArrayRef = minicbor::decode(&buf).unwrap();
+    assert_eq!(borrowed.as_bytes(), array.as_bytes());
+
+    let streamed = ElementReader::new(IoSource(&buf[..]))
+        .unwrap()
+        .read_to_end()
+        .unwrap();
+    assert_eq!(streamed, array);
+
+    let found = locate(&buf).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!((found[0].offset, found[0].end), (0, buf.len()));
+    assert_eq!(found[0].element_type, array.element_type());
+});
@@ -16,6 +16,8 @@ cargo build --no-default-features --features half
 echo "==> build: bare-metal (thumbv7em-none-eabi, no alloc)"
 cargo build --no-default-features --target thumbv7em-none-eabi
 cargo build --no-default-features --features half --target thumbv7em-none-eabi
+cargo build --no-default-features --features embedded-io --target thumbv7em-none-eabi
+cargo build --no-default-features --features defmt,half --target thumbv7em-none-eabi
 
 echo "==> test (all features)"
 cargo test --all-features
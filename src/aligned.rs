@@ -0,0 +1,140 @@
+use crate::element::{Endianness, Scalar};
+use crate::typed_array::TypedArray;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Owned byte storage aligned for every element type.
+///
+/// Backed by a `Vec<u64>`, so the payload always starts on an 8-byte boundary.
+/// A native-endian [`AlignedTypedArray`] can therefore always be viewed as
+/// `&[T]`/`&mut [T]` through [`TypedArray::as_native_slice`] and
+/// [`TypedArray::as_native_mut_slice`].
+#[derive(Clone, Default)]
+pub struct AlignedBytes {
+    words: Vec<u64>,
+    len: usize,
+}
+
+/// A typed array owning an aligned byte payload.
+pub type AlignedTypedArray = TypedArray<AlignedBytes>;
+
+impl AlignedBytes {
+    /// `len` zero bytes.
+    pub fn zeroed(len: usize) -> Self {
+        Self {
+            words: alloc::vec![0; len.div_ceil(8)],
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The backing words; bytes past `len` are zero.
+    #[cfg(feature = "arrow")]
+    pub(crate) fn into_words(self) -> Vec<u64> {
+        self.words
+    }
+}
+
+impl AsRef<[u8]> for AlignedBytes {
+    fn as_ref(&self) -> &[u8] {
+        // SAFETY: `words` holds at least `len` initialized bytes, and `u8` has
+        // no alignment or validity requirements.
+        unsafe { core::slice::from_raw_parts(self.words.as_ptr().cast::<u8>(), self.len) }
+    }
+}
+
+impl AsMut<[u8]> for AlignedBytes {
+    fn as_mut(&mut self) -> &mut [u8] {
+        // SAFETY: as in `as_ref`; the borrow of `self` is unique.
+        unsafe { core::slice::from_raw_parts_mut(self.words.as_mut_ptr().cast::<u8>(), self.len) }
+    }
+}
+
+impl From<&[u8]> for AlignedBytes {
+    fn from(bytes: &[u8]) -> Self {
+        let mut out = Self::zeroed(bytes.len());
+        out.as_mut().copy_from_slice(bytes);
+        out
+    }
+}
+
+impl PartialEq for AlignedBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl Eq for AlignedBytes {}
+
+impl fmt::Debug for AlignedBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_ref()).finish()
+    }
+}
+
+impl TypedArray<AlignedBytes> {
+    /// Build an aligned typed array from native scalar values, laying them
+    /// out in the requested endianness.
+    pub fn from_slice_aligned<T: Scalar>(values: &[T], endianness: Endianness) -> Self {
+        let mut bytes = AlignedBytes::zeroed(core::mem::size_of_val(values));
+        TypedArray::write_into(values, endianness, bytes.as_mut())
+            .expect("aligned buffer has exactly the needed length");
+        // Length is always a multiple of the width here, so `new` cannot fail.
+        TypedArray::new(T::ELEMENT_TYPE, endianness, bytes)
+            .expect("from_slice_aligned produces valid length")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ElementType, OwnedTypedArray};
+
+    #[test]
+    fn storage_is_aligned() {
+        for len in [0, 1, 7, 8, 9, 17] {
+            let b = AlignedBytes::zeroed(len);
+            assert_eq!(b.len(), len);
+            assert_eq!(b.as_ref().as_ptr() as usize % 8, 0);
+        }
+    }
+
+    #[test]
+    fn native_arrays_view_as_slices() {
+        let mut a = TypedArray::from_slice_aligned(&[1.5f64, -2.0, 3.0], Endianness::NATIVE);
+        assert_eq!(a.as_native_slice::<f64>(), Some(&[1.5, -2.0, 3.0][..]));
+        a.as_native_mut_slice::<f64>().unwrap()[1] = 4.0;
+        assert_eq!(a.as_native_slice::<f64>(), Some(&[1.5, 4.0, 3.0][..]));
+        assert_eq!(a.as_native_slice::<u64>(), None);
+    }
+
+    #[test]
+    fn foreign_endianness_has_no_view() {
+        let foreign = match Endianness::NATIVE {
+            Endianness::Big => Endianness::Little,
+            Endianness::Little => Endianness::Big,
+        };
+        let a = TypedArray::from_slice_aligned(&[1u32, 2], foreign);
+        assert_eq!(a.as_native_slice::<u32>(), None);
+        let b = TypedArray::from_slice_aligned(&[1u8, 2], foreign);
+        assert_eq!(b.as_native_slice::<u8>(), Some(&[1u8, 2][..]));
+    }
+
+    #[test]
+    fn decodes_into_aligned_storage() {
+        let owned = OwnedTypedArray::from_slice(&[7i16, -8], Endianness::NATIVE);
+        let mut buf = alloc::vec::Vec::new();
+        minicbor::encode(&owned, &mut buf).unwrap();
+        let aligned: AlignedTypedArray = minicbor::decode(&buf).unwrap();
+        assert_eq!(aligned.element_type(), ElementType::I16);
+        assert_eq!(aligned.as_bytes(), owned.as_bytes());
+        assert_eq!(aligned.as_native_slice::<i16>(), Some(&[7, -8][..]));
+    }
+}
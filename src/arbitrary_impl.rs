@@ -0,0 +1,85 @@
+use crate::element::{Element, ElementType, Endianness};
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Take an element type, an endianness and a whole number of elements' worth
+/// of payload bytes from `u`.
+fn arbitrary_parts<'a>(u: &mut Unstructured<'a>) -> Result<(ElementType, Endianness, &'a [u8])> {
+    let element_type: ElementType = u.arbitrary()?;
+    let endianness = u.arbitrary()?;
+    let width = element_type.width();
+    let len = u.arbitrary_len::<u8>()? / width;
+    Ok((element_type, endianness, u.bytes(len * width)?))
+}
+
+/// Any element type with any bit pattern, NaNs included.
+impl<'a> Arbitrary<'a> for Element {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let element_type: ElementType = u.arbitrary()?;
+        let bytes = u.bytes(element_type.width())?;
+        Ok(element_type.decode_chunk(bytes, Endianness::Big))
+    }
+}
+
+/// Borrows its payload from the fuzzer input.
+impl<'a> Arbitrary<'a> for TypedArray<&'a [u8]> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (element_type, endianness, bytes) = arbitrary_parts(u)?;
+        Ok(TypedArray::new(element_type, endianness, bytes).expect("whole elements"))
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedTypedArray {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (element_type, endianness, bytes) = arbitrary_parts(u)?;
+        Ok(TypedArray::new(element_type, endianness, bytes.to_vec()).expect("whole elements"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TypedArrayRef;
+    use alloc::vec::Vec;
+
+    fn seeds() -> impl Iterator<Item = Vec<u8>> {
+        (0u32..200).map(|seed| {
+            (0..seed % 97)
+                .map(|i| (seed.wrapping_mul(31).wrapping_add(i * 7)) as u8)
+                .collect()
+        })
+    }
+
+    #[test]
+    fn arrays_always_have_valid_lengths() {
+        for data in seeds() {
+            let mut u = Unstructured::new(&data);
+            let Ok(a) = TypedArrayRef::arbitrary(&mut u) else {
+                continue;
+            };
+            assert_eq!(a.as_bytes().len() % a.element_type().width(), 0);
+            assert_eq!(a.iter().count(), a.len());
+        }
+    }
+
+    #[test]
+    fn elements_cover_every_type() {
+        let mut seen = Vec::new();
+        for data in seeds() {
+            if let Ok(e) = Element::arbitrary(&mut Unstructured::new(&data)) {
+                seen.push(e.element_type());
+            }
+        }
+        assert!(ElementType::ALL.iter().all(|t| seen.contains(t)));
+    }
+
+    #[test]
+    fn owned_arrays_round_trip() {
+        for data in seeds() {
+            let a = OwnedTypedArray::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            let mut buf = Vec::new();
+            minicbor::encode(&a, &mut buf).unwrap();
+            assert_eq!(minicbor::decode::<OwnedTypedArray>(&buf).unwrap(), a);
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use crate::element::{Element, ElementType, Endianness, Value};
+use crate::typed_array::{OwnedTypedArray, TypedArray, TypedArrayRef};
+use core::fmt;
+
+/// An element-wise arithmetic operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    /// Integer division truncates toward zero.
+    Div,
+}
+
+/// What happens when an integer result does not fit the result type.
+///
+/// [`ElementType::U8Clamped`] results always clamp to 0–255.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// Fail with [`ArithmeticError::Overflow`].
+    #[default]
+    Error,
+    /// Keep the low bits, as two's complement arithmetic does.
+    Wrap,
+    /// Clamp to the result type's range.
+    Saturate,
+}
+
+/// How [`TypedArray::elementwise`] lays out and checks its result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArithmeticOptions {
+    /// Byte order of the result.
+    pub endianness: Endianness,
+    pub overflow: Overflow,
+}
+
+impl Default for ArithmeticOptions {
+    /// Little-endian, failing on overflow.
+    fn default() -> Self {
+        ArithmeticOptions {
+            endianness: Endianness::Little,
+            overflow: Overflow::Error,
+        }
+    }
+}
+
+/// Error from element-wise arithmetic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The two arrays differ in length.
+    Length { left: usize, right: usize },
+    /// The integer result at `index` does not fit the result type.
+    Overflow { index: usize },
+    /// Integer division by zero at `index`.
+    DivideByZero { index: usize },
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArithmeticError::Length { left, right } => {
+                write!(f, "arrays of {left} and {right} elements")
+            }
+            ArithmeticError::Overflow { index } => write!(f, "overflow at element {index}"),
+            ArithmeticError::DivideByZero { index } => {
+                write!(f, "division by zero at element {index}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArithmeticError {}
+
+/// The right-hand side of an element-wise operation: an array of the same
+/// length, or one value applied to every element.
+#[derive(Clone, Debug)]
+pub enum Operand<'a> {
+    Array(TypedArrayRef<'a>),
+    Scalar(Element),
+}
+
+impl<'a, C: AsRef<[u8]>> From<&'a TypedArray<C>> for Operand<'a> {
+    fn from(array: &'a TypedArray<C>) -> Self {
+        Operand::Array(
+            TypedArray::new(array.element_type(), array.endianness(), array.as_bytes())
+                .expect("whole elements"),
+        )
+    }
+}
+
+impl From<Element> for Operand<'_> {
+    fn from(scalar: Element) -> Self {
+        Operand::Scalar(scalar)
+    }
+}
+
+impl ElementType {
+    /// The type element-wise arithmetic between `self` and `other` yields.
+    ///
+    /// - Equal types yield themselves.
+    /// - Integers of the same signedness yield the wider type; mixed
+    ///   signedness yields a signed type wider than the unsigned one, at
+    ///   most `i64`. [`ElementType::U8Clamped`] counts as `u8` unless both
+    ///   sides are clamped.
+    /// - Floats yield the wider type; `f16` with `bf16` yields `f32`.
+    /// - An integer with a float yields the narrowest float at least as wide
+    ///   as the float that holds every value of the integer type exactly
+    ///   (`f64` for 32- and 64-bit integers, which is lossy for the latter).
+    pub fn promote(self, other: ElementType) -> ElementType {
+        if self == other {
+            return self;
+        }
+        match (is_float(self), is_float(other)) {
+            (false, false) => promote_integers(self, other),
+            (true, true) if self.width() == other.width() => ElementType::F32,
+            (true, true) => wider(self, other),
+            (true, false) => promote_mixed(other, self),
+            (false, true) => promote_mixed(self, other),
+        }
+    }
+}
+
+fn is_float(t: ElementType) -> bool {
+    match t {
+        #[cfg(feature = "half")]
+        ElementType::F16 | ElementType::Bf16 => true,
+        ElementType::F32 | ElementType::F64 => true,
+        _ => false,
+    }
+}
+
+fn is_signed(t: ElementType) -> bool {
+    matches!(
+        t,
+        ElementType::I8 | ElementType::I16 | ElementType::I32 | ElementType::I64
+    )
+}
+
+fn wider(a: ElementType, b: ElementType) -> ElementType {
+    if a.width() >= b.width() {
+        a
+    } else {
+        b
+    }
+}
+
+fn promote_integers(a: ElementType, b: ElementType) -> ElementType {
+    let unclamp = |t| match t {
+        ElementType::U8Clamped => ElementType::U8,
+        t => t,
+    };
+    let (a, b) = (unclamp(a), unclamp(b));
+    let (signed, unsigned) = match (is_signed(a), is_signed(b)) {
+        (true, false) => (a, b),
+        (false, true) => (b, a),
+        _ => return wider(a, b),
+    };
+    if signed.width() > unsigned.width() {
+        return signed;
+    }
+    match unsigned.width() {
+        1 => ElementType::I16,
+        2 => ElementType::I32,
+        _ => ElementType::I64,
+    }
+}
+
+fn promote_mixed(integer: ElementType, float: ElementType) -> ElementType {
+    let exact = match integer.width() {
+        1 => float,
+        2 => ElementType::F32,
+        _ => ElementType::F64,
+    };
+    wider(float, exact)
+}
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// Apply `op` to each element and the matching element of `rhs`, or
+    /// `rhs` itself if it is a scalar.
+    ///
+    /// The result has the type [`ElementType::promote`] gives for the two
+    /// operand types. Floats follow IEEE 754, computed in `f64` and rounded
+    /// to the result type. Integers are computed exactly, then fitted to the
+    /// result type per [`ArithmeticOptions::overflow`]; dividing an integer
+    /// by zero is always an error.
+    pub fn elementwise<'a>(
+        &self,
+        op: Op,
+        rhs: impl Into<Operand<'a>>,
+        options: &ArithmeticOptions,
+    ) -> Result<OwnedTypedArray, ArithmeticError> {
+        let rhs = rhs.into();
+        let rhs_type = match &rhs {
+            Operand::Array(array) if array.len() != self.len() => {
+                return Err(ArithmeticError::Length {
+                    left: self.len(),
+                    right: array.len(),
+                });
+            }
+            Operand::Array(array) => array.element_type(),
+            Operand::Scalar(scalar) => scalar.element_type(),
+        };
+        let result_type = self.element_type().promote(rhs_type);
+        let width = result_type.width();
+        let mut bytes = alloc::vec![0; self.len() * width];
+        for (index, (a, out)) in self.iter().zip(bytes.chunks_exact_mut(width)).enumerate() {
+            let b = match &rhs {
+                Operand::Array(array) => array.element(index),
+                Operand::Scalar(scalar) => *scalar,
+            };
+            apply(op, a.value(), b.value(), result_type, options.overflow)
+                .map_err(|e| e.at(index))?
+                .write_into(options.endianness, out);
+        }
+        Ok(TypedArray::new(result_type, options.endianness, bytes).expect("whole elements"))
+    }
+
+    /// `self + rhs`, as [`TypedArray::elementwise`] defines.
+    pub fn add<'a>(
+        &self,
+        rhs: impl Into<Operand<'a>>,
+        options: &ArithmeticOptions,
+    ) -> Result<OwnedTypedArray, ArithmeticError> {
+        self.elementwise(Op::Add, rhs, options)
+    }
+
+    /// `self - rhs`, as [`TypedArray::elementwise`] defines.
+    pub fn sub<'a>(
+        &self,
+        rhs: impl Into<Operand<'a>>,
+        options: &ArithmeticOptions,
+    ) -> Result<OwnedTypedArray, ArithmeticError> {
+        self.elementwise(Op::Sub, rhs, options)
+    }
+
+    /// `self * rhs`, as [`TypedArray::elementwise`] defines.
+    pub fn mul<'a>(
+        &self,
+        rhs: impl Into<Operand<'a>>,
+        options: &ArithmeticOptions,
+    ) -> Result<OwnedTypedArray, ArithmeticError> {
+        self.elementwise(Op::Mul, rhs, options)
+    }
+
+    /// `self / rhs`, as [`TypedArray::elementwise`] defines.
+    pub fn div<'a>(
+        &self,
+        rhs: impl Into<Operand<'a>>,
+        options: &ArithmeticOptions,
+    ) -> Result<OwnedTypedArray, ArithmeticError> {
+        self.elementwise(Op::Div, rhs, options)
+    }
+}
+
+/// A failure at an element whose index is not yet known.
+enum Failure {
+    Overflow,
+    DivideByZero,
+}
+
+impl Failure {
+    fn at(self, index: usize) -> ArithmeticError {
+        match self {
+            Failure::Overflow => ArithmeticError::Overflow { index },
+            Failure::DivideByZero => ArithmeticError::DivideByZero { index },
+        }
+    }
+}
+
+fn apply(
+    op: Op,
+    a: Value,
+    b: Value,
+    to: ElementType,
+    overflow: Overflow,
+) -> Result<Element, Failure> {
+    let (Value::Int(a), Value::Int(b)) = (a, b) else {
+        let (a, b) = (a.to_f64(), b.to_f64());
+        let v = match op {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+            Op::Div => a / b,
+        };
+        return Ok(Value::Float(v)
+            .to_element(to)
+            .expect("floats convert to floats"));
+    };
+    // Operands fit in 64 bits, so only multiplication can overflow `i128`.
+    let v = match op {
+        Op::Add => a + b,
+        Op::Sub => a - b,
+        Op::Mul => match (a.checked_mul(b), overflow) {
+            (Some(v), _) => v,
+            (None, Overflow::Error) => return Err(Failure::Overflow),
+            (None, Overflow::Wrap) => a.wrapping_mul(b),
+            (None, Overflow::Saturate) if (a < 0) != (b < 0) => i128::MIN,
+            (None, Overflow::Saturate) => i128::MAX,
+        },
+        Op::Div if b == 0 => return Err(Failure::DivideByZero),
+        Op::Div => a / b,
+    };
+    narrow(v, to, overflow).ok_or(Failure::Overflow)
+}
+
+/// Fit an exact integer result to the integer type `to`.
+fn narrow(v: i128, to: ElementType, overflow: Overflow) -> Option<Element> {
+    macro_rules! fit {
+        ($variant:ident, $ty:ty) => {
+            Element::$variant(match overflow {
+                Overflow::Error => <$ty>::try_from(v).ok()?,
+                Overflow::Wrap => v as $ty,
+                Overflow::Saturate => v.clamp(<$ty>::MIN.into(), <$ty>::MAX.into()) as $ty,
+            })
+        };
+    }
+    Some(match to {
+        ElementType::U8 => fit!(U8, u8),
+        ElementType::U16 => fit!(U16, u16),
+        ElementType::U32 => fit!(U32, u32),
+        ElementType::U64 => fit!(U64, u64),
+        ElementType::I8 => fit!(I8, i8),
+        ElementType::I16 => fit!(I16, i16),
+        ElementType::I32 => fit!(I32, i32),
+        ElementType::I64 => fit!(I64, i64),
+        // Clamps regardless of `overflow`.
+        _ => Value::Int(v).to_element(to)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn promotes_to_a_common_type() {
+        use ElementType::*;
+        assert_eq!(U8.promote(U16), U16);
+        assert_eq!(U8.promote(I8), I16);
+        assert_eq!(U32.promote(I64), I64);
+        assert_eq!(U64.promote(I8), I64);
+        assert_eq!(U8Clamped.promote(U8Clamped), U8Clamped);
+        assert_eq!(U8Clamped.promote(I8), I16);
+        assert_eq!(I16.promote(F32), F32);
+        assert_eq!(I32.promote(F32), F64);
+        assert_eq!(F32.promote(F64), F64);
+        #[cfg(feature = "half")]
+        {
+            assert_eq!(U8.promote(Bf16), Bf16);
+            assert_eq!(I16.promote(F16), F32);
+            assert_eq!(F16.promote(Bf16), F32);
+        }
+    }
+
+    #[test]
+    fn arrays_and_scalars() {
+        let a = TypedArray::from_slice(&[1u8, 200], Endianness::Big);
+        let b = TypedArray::from_slice(&[-3i16, 4], Endianness::Little);
+        let options = ArithmeticOptions {
+            endianness: Endianness::Big,
+            ..ArithmeticOptions::default()
+        };
+        let sum = a.add(&b, &options).unwrap();
+        assert_eq!(sum.endianness(), Endianness::Big);
+        assert_eq!(sum.to_vec::<i16>().unwrap(), [-2, 204]);
+        assert_eq!(
+            b.div(Element::I16(-2), &options)
+                .unwrap()
+                .to_vec::<i16>()
+                .unwrap(),
+            [1, -2]
+        );
+
+        let x = TypedArray::from_slice(&[1.5f32, -2.0], Endianness::Little);
+        let scaled = x
+            .mul(Element::F32(2.0), &ArithmeticOptions::default())
+            .unwrap();
+        assert_eq!(scaled.to_vec::<f32>().unwrap(), [3.0, -4.0]);
+        let shifted = x.sub(&a, &ArithmeticOptions::default()).unwrap();
+        assert_eq!(shifted.to_vec::<f32>().unwrap(), [0.5, -202.0]);
+        let inf = x
+            .div(Element::F32(0.0), &ArithmeticOptions::default())
+            .unwrap();
+        assert_eq!(
+            inf.to_vec::<f32>().unwrap(),
+            [f32::INFINITY, f32::NEG_INFINITY]
+        );
+
+        let three = TypedArray::from_slice(&[1u8, 2, 3], Endianness::Big);
+        assert_eq!(
+            a.add(&three, &options),
+            Err(ArithmeticError::Length { left: 2, right: 3 })
+        );
+    }
+
+    #[test]
+    fn overflow_follows_the_policy() {
+        let a = TypedArray::from_slice(&[100u8, 200], Endianness::Big);
+        let policy = |overflow| ArithmeticOptions {
+            overflow,
+            ..ArithmeticOptions::default()
+        };
+        assert_eq!(
+            a.mul(Element::U8(2), &policy(Overflow::Error)),
+            Err(ArithmeticError::Overflow { index: 1 })
+        );
+        let wrapped = a.mul(Element::U8(2), &policy(Overflow::Wrap)).unwrap();
+        assert_eq!(wrapped.to_vec::<u8>().unwrap(), [200, 144]);
+        let saturated = a.mul(Element::U8(2), &policy(Overflow::Saturate)).unwrap();
+        assert_eq!(saturated.to_vec::<u8>().unwrap(), [200, 255]);
+
+        let big = TypedArray::from_slice(&[u64::MAX, 2], Endianness::Little);
+        let squared = big.mul(&big, &policy(Overflow::Wrap)).unwrap();
+        assert_eq!(squared.to_vec::<u64>().unwrap(), [1, 4]);
+        let squared = big.mul(&big, &policy(Overflow::Saturate)).unwrap();
+        assert_eq!(squared.to_vec::<u64>().unwrap(), [u64::MAX, 4]);
+
+        let clamped =
+            TypedArray::new(ElementType::U8Clamped, Endianness::Big, &[250u8][..]).unwrap();
+        let sum = clamped.add(&clamped, &policy(Overflow::Error)).unwrap();
+        assert_eq!(sum.as_bytes(), [255]);
+        assert_eq!(
+            big.div(Element::U64(0), &policy(Overflow::Wrap)),
+            Err(ArithmeticError::DivideByZero { index: 0 })
+        );
+    }
+}
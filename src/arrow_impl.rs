@@ -0,0 +1,150 @@
+use crate::aligned::AlignedBytes;
+use crate::convert::ConvertError;
+use crate::element::{ElementType, Endianness, Scalar};
+use crate::typed_array::{InvalidLength, OwnedTypedArray, TypedArray};
+use alloc::vec::Vec;
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::PrimitiveArray;
+use arrow_buffer::{Buffer, ScalarBuffer};
+
+// Arrow keeps values in native byte order (its IPC format is little-endian),
+// so a payload can be handed over without copying when it is native-endian
+// and its buffer is aligned for the value type.
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// Copy the elements into an Arrow primitive array, byte-swapping if the
+    /// payload is not native-endian.
+    ///
+    /// `A::Native` must back the element type; `Float16Type` maps to
+    /// [`ElementType::F16`].
+    pub fn to_arrow<A>(&self) -> Result<PrimitiveArray<A>, ConvertError>
+    where
+        A: ArrowPrimitiveType,
+        A::Native: Scalar,
+    {
+        Ok(PrimitiveArray::new(
+            ScalarBuffer::from(self.to_vec()?),
+            None,
+        ))
+    }
+}
+
+impl OwnedTypedArray {
+    /// Convert into an Arrow primitive array, reusing the payload allocation
+    /// when it is native-endian and aligned for `A::Native`, and copying
+    /// otherwise. See [`TypedArray::to_arrow`].
+    pub fn into_arrow<A>(self) -> Result<PrimitiveArray<A>, ConvertError>
+    where
+        A: ArrowPrimitiveType,
+        A::Native: Scalar,
+    {
+        if self.as_native_slice::<A::Native>().is_none() {
+            return self.to_arrow();
+        }
+        let len = self.len();
+        Ok(PrimitiveArray::new(
+            ScalarBuffer::new(Buffer::from_vec(self.into_bytes()), 0, len),
+            None,
+        ))
+    }
+
+    /// Copy an Arrow primitive array's values into a typed array laid out in
+    /// `endianness`.
+    ///
+    /// Typed arrays have no nulls: null slots keep whatever value Arrow stores
+    /// underneath them, so check `null_count()` first if that matters.
+    pub fn from_arrow<A>(array: &PrimitiveArray<A>, endianness: Endianness) -> Self
+    where
+        A: ArrowPrimitiveType,
+        A::Native: Scalar,
+    {
+        TypedArray::from_slice(array.values(), endianness)
+    }
+
+    /// Copy a raw Arrow buffer of native-endian `element_type` values.
+    pub fn from_arrow_buffer(
+        buffer: &Buffer,
+        element_type: ElementType,
+    ) -> Result<Self, InvalidLength> {
+        TypedArray::new(element_type, Endianness::NATIVE, buffer.as_slice().to_vec())
+    }
+}
+
+impl TypedArray<AlignedBytes> {
+    /// Convert into an Arrow primitive array without copying when
+    /// native-endian, since aligned storage is always suitably aligned. See
+    /// [`TypedArray::to_arrow`].
+    pub fn into_arrow<A>(self) -> Result<PrimitiveArray<A>, ConvertError>
+    where
+        A: ArrowPrimitiveType,
+        A::Native: Scalar,
+    {
+        if self.as_native_slice::<A::Native>().is_none() {
+            return self.to_arrow();
+        }
+        let len = self.len();
+        let words: Vec<u64> = self.into_bytes().into_words();
+        Ok(PrimitiveArray::new(
+            ScalarBuffer::new(Buffer::from_vec(words), 0, len),
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::types::{Float16Type, Float32Type, Int16Type, UInt64Type};
+
+    fn foreign() -> Endianness {
+        match Endianness::NATIVE {
+            Endianness::Big => Endianness::Little,
+            Endianness::Little => Endianness::Big,
+        }
+    }
+
+    #[test]
+    fn native_aligned_payload_is_not_copied() {
+        let a = TypedArray::from_slice_aligned(&[1.0f32, 2.0, 3.0], Endianness::NATIVE);
+        let ptr = a.as_bytes().as_ptr();
+        let arrow = a.into_arrow::<Float32Type>().unwrap();
+        assert_eq!(arrow.values().as_ref(), &[1.0, 2.0, 3.0]);
+        assert_eq!(arrow.values().inner().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn foreign_endianness_is_byte_swapped() {
+        let a = TypedArray::from_slice(&[1i16, -2], foreign());
+        assert_eq!(
+            a.to_arrow::<Int16Type>().unwrap().values().as_ref(),
+            &[1, -2]
+        );
+        assert_eq!(
+            a.into_arrow::<Int16Type>().unwrap().values().as_ref(),
+            &[1, -2]
+        );
+        let b = TypedArray::from_slice_aligned(&[7u64], foreign());
+        assert_eq!(b.into_arrow::<UInt64Type>().unwrap().value(0), 7);
+    }
+
+    #[test]
+    fn round_trips_f16() {
+        let values = [half::f16::from_f32(0.5), half::f16::from_f32(-4.0)];
+        let a = TypedArray::from_slice(&values, Endianness::Little);
+        let arrow = a.to_arrow::<Float16Type>().unwrap();
+        assert_eq!(arrow.len(), 2);
+        let back = OwnedTypedArray::from_arrow(&arrow, Endianness::Little);
+        assert_eq!(back.element_type(), ElementType::F16);
+        assert_eq!(back, a);
+    }
+
+    #[test]
+    fn checks_element_type_and_buffer_length() {
+        let a = TypedArray::from_slice(&[1u64], Endianness::Big);
+        assert!(a.to_arrow::<Int16Type>().is_err());
+        let buffer = Buffer::from_vec(alloc::vec![1u16, 2]);
+        let back = OwnedTypedArray::from_arrow_buffer(&buffer, ElementType::U16).unwrap();
+        assert_eq!(back.to_vec::<u16>().unwrap(), [1, 2]);
+        assert!(OwnedTypedArray::from_arrow_buffer(&buffer, ElementType::U64).is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::element::{Element, ElementType, Endianness, Scalar, MAX_WIDTH};
+use crate::read::{Framing, Head, ReadError};
+use crate::tag::{encode_tag, Bf16Encoding, TagResolver};
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use core::future::poll_fn;
+use core::pin::Pin;
+use futures_io::{AsyncRead, AsyncWrite};
+use minicbor::decode;
+use minicbor::encode::write::Cursor;
+use std::io;
+
+/// Incremental decoder for one typed array read from a `futures_io::AsyncRead`.
+///
+/// The async counterpart of [`crate::ElementReader`]: parses the typed-array
+/// header up front, then decodes elements or fixed-size chunks as bytes
+/// arrive, without blocking or buffering the whole payload. Tokio readers can
+/// be used through `tokio_util::compat`.
+#[derive(Debug)]
+pub struct AsyncElementReader<R> {
+    reader: R,
+    framing: Framing,
+}
+
+impl<R: AsyncRead + Unpin> AsyncElementReader<R> {
+    /// Read the typed-array tag and byte-string header from `reader`.
+    pub async fn new(mut reader: R) -> Result<Self, ReadError<io::Error>> {
+        let mut tags = TagResolver::new(Bf16Encoding::default());
+        let resolved = loop {
+            if let Some(resolved) = Framing::resolve(&mut tags, read_head(&mut reader).await?)? {
+                break resolved;
+            }
+        };
+        let framing = Framing::start(resolved, read_head(&mut reader).await?)?;
+        Ok(Self { reader, framing })
+    }
+
+    pub fn element_type(&self) -> ElementType {
+        self.framing.element_type
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        self.framing.endianness
+    }
+
+    /// Total number of elements, if the payload has a definite length.
+    pub fn definite_len(&self) -> Option<usize> {
+        self.framing.len
+    }
+
+    /// Give back the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the raw bytes of the next element into `out[..width]`. Returns
+    /// `false` at the end of the payload.
+    async fn next_raw(&mut self, out: &mut [u8; MAX_WIDTH]) -> Result<bool, ReadError<io::Error>> {
+        let width = self.framing.element_type.width();
+        let mut filled = 0;
+        while filled < width {
+            if self.framing.needs_segment() {
+                let head = read_head(&mut self.reader).await?;
+                if !self.framing.next_segment(head)? {
+                    return Ok(self.framing.finish(filled)?);
+                }
+                continue;
+            }
+            if self.framing.segment == 0 {
+                return Ok(self.framing.finish(filled)?);
+            }
+            let take = self.framing.take(filled);
+            read_exact(&mut self.reader, &mut out[filled..filled + take]).await?;
+            filled += take;
+        }
+        Ok(true)
+    }
+
+    /// Decode the next element, or `None` at the end of the payload.
+    pub async fn next_element(&mut self) -> Result<Option<Element>, ReadError<io::Error>> {
+        let mut buf = [0u8; MAX_WIDTH];
+        if !self.next_raw(&mut buf).await? {
+            return Ok(None);
+        }
+        let width = self.framing.element_type.width();
+        Ok(Some(
+            self.framing
+                .element_type
+                .decode_chunk(&buf[..width], self.framing.endianness),
+        ))
+    }
+
+    /// Decode up to `out.len()` elements into `out`, returning how many were
+    /// written; `0` means the payload is exhausted (or `out` is empty).
+    ///
+    /// `T` must back the array's element type.
+    pub async fn read_chunk<T: Scalar>(
+        &mut self,
+        out: &mut [T],
+    ) -> Result<usize, ReadError<io::Error>> {
+        if !self.framing.element_type.is_backed_by::<T>() {
+            return Err(decode::Error::message("scalar type does not match element type").into());
+        }
+        let width = self.framing.element_type.width();
+        let mut buf = [0u8; MAX_WIDTH];
+        for (n, slot) in out.iter_mut().enumerate() {
+            if !self.next_raw(&mut buf).await? {
+                return Ok(n);
+            }
+            *slot = self.framing.decode(&buf[..width]);
+        }
+        Ok(out.len())
+    }
+
+    /// Read the rest of the payload into an owned typed array.
+    pub async fn read_to_end(mut self) -> Result<OwnedTypedArray, ReadError<io::Error>> {
+        let width = self.framing.element_type.width();
+        let mut bytes = alloc::vec::Vec::with_capacity(self.framing.capacity_hint());
+        let mut buf = [0u8; MAX_WIDTH];
+        while self.next_raw(&mut buf).await? {
+            bytes.extend_from_slice(&buf[..width]);
+        }
+        // Only whole elements are collected, so `new` cannot fail.
+        Ok(
+            TypedArray::new(self.framing.element_type, self.framing.endianness, bytes)
+                .expect("read_to_end collects whole elements"),
+        )
+    }
+}
+
+/// Encode a typed array to a `futures_io::AsyncWrite`.
+///
+/// Writes the tag and byte-string header followed by the payload; the writer
+/// is not flushed.
+pub async fn encode_async<C, W>(array: &TypedArray<C>, writer: &mut W) -> io::Result<()>
+where
+    C: AsRef<[u8]>,
+    W: AsyncWrite + Unpin,
+{
+    // Up to two tag heads (a bf16 marker) and a byte-string head, at most 9
+    // bytes each.
+    let mut e = minicbor::Encoder::new(Cursor::new([0u8; 27]));
+    encode_tag(
+        &mut e,
+        array.element_type(),
+        array.endianness(),
+        Bf16Encoding::default(),
+    )
+    .and_then(|()| e.bytes_len(array.as_bytes().len() as u64).map(drop))
+    .expect("typed-array header fits in 27 bytes");
+    let header = e.into_writer();
+    write_all(writer, &header.get_ref()[..header.position()]).await?;
+    write_all(writer, array.as_bytes()).await
+}
+
+async fn read_head<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Head, ReadError<io::Error>> {
+    let mut initial = [0u8; 1];
+    read_exact(reader, &mut initial).await?;
+    let mut arg = [0u8; 8];
+    let n = Head::arg_len(initial[0] & 0x1f)?;
+    read_exact(reader, &mut arg[..n]).await?;
+    Ok(Head::parse(initial[0], &arg[..n]))
+}
+
+async fn read_exact<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    mut buf: &mut [u8],
+) -> Result<(), ReadError<io::Error>> {
+    while !buf.is_empty() {
+        let n = poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, buf))
+            .await
+            .map_err(ReadError::Io)?;
+        if n == 0 {
+            return Err(decode::Error::end_of_input().into());
+        }
+        buf = &mut buf[n..];
+    }
+    Ok(())
+}
+
+async fn write_all<W: AsyncWrite + Unpin>(writer: &mut W, mut buf: &[u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        let n = poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, buf)).await?;
+        if n == 0 {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        buf = &buf[n..];
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use futures::executor::block_on;
+    use futures::io::Cursor;
+
+    fn encoded<T: Scalar>(values: &[T], endianness: Endianness) -> Vec<u8> {
+        let mut out = Vec::new();
+        block_on(encode_async(
+            &TypedArray::from_slice(values, endianness),
+            &mut out,
+        ))
+        .unwrap();
+        out
+    }
+
+    #[test]
+    fn encode_matches_minicbor() {
+        let values: Vec<u16> = (0..300).collect();
+        let array = TypedArray::from_slice(&values, Endianness::Little);
+        let mut expected = Vec::new();
+        minicbor::encode(&array, &mut expected).unwrap();
+        assert_eq!(encoded(&values, Endianness::Little), expected);
+    }
+
+    #[test]
+    fn round_trips_to_owned() {
+        let buf = encoded(&[1.0f32, -2.5], Endianness::Big);
+        let owned = block_on(async {
+            let r = AsyncElementReader::new(Cursor::new(&buf)).await?;
+            assert_eq!(r.definite_len(), Some(2));
+            r.read_to_end().await
+        })
+        .unwrap();
+        assert_eq!(
+            owned,
+            TypedArray::from_slice(&[1.0f32, -2.5], Endianness::Big)
+        );
+    }
+
+    #[test]
+    fn reads_elements_and_chunks() {
+        let buf = encoded(&[1i64, 2, 3], Endianness::Little);
+        block_on(async {
+            let mut r = AsyncElementReader::new(Cursor::new(&buf)).await.unwrap();
+            assert_eq!(r.element_type(), ElementType::I64);
+            assert_eq!(r.endianness(), Endianness::Little);
+            assert_eq!(r.next_element().await.unwrap(), Some(Element::I64(1)));
+            let mut chunk = [0i64; 4];
+            assert_eq!(r.read_chunk(&mut chunk).await.unwrap(), 2);
+            assert_eq!(&chunk[..2], &[2, 3]);
+            assert_eq!(r.next_element().await.unwrap(), None);
+            assert!(r.read_chunk(&mut [0u8; 1]).await.is_err());
+        });
+    }
+
+    #[test]
+    fn huge_declared_length_is_not_preallocated() {
+        let header = [0xd8, 0x40, 0x5b, 0x40, 0, 0, 0, 0, 0, 0, 0];
+        let r = block_on(async {
+            let r = AsyncElementReader::new(Cursor::new(&header[..])).await?;
+            r.read_to_end().await
+        });
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn reports_truncated_input() {
+        let buf = encoded(&[1u32, 2], Endianness::Big);
+        let r = block_on(async {
+            let r = AsyncElementReader::new(Cursor::new(&buf[..buf.len() - 2])).await?;
+            r.read_to_end().await
+        });
+        assert!(matches!(r, Err(ReadError::Decode(e)) if e.is_end_of_input()));
+    }
+}
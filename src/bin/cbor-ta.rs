@@ -0,0 +1,287 @@
+//! `cbor-ta`: list the typed arrays inside a CBOR file or CBOR sequence.
+
+use clap::{Args, Parser, Subcommand};
+use minicbor::{Decoder, Encoder};
+use minicbor_typed_arrays::{
+    read_csv, write_csv, ColumnRef, CsvOptions, ElementType, Endianness, NanPolicy, TypedArrayRef,
+    TypedArrays,
+};
+use std::fmt::Write as _;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// List every RFC8746 typed array in a CBOR file or CBOR sequence, with its
+/// path, element type, endianness, length, min/max/mean and first values.
+#[derive(Parser)]
+#[command(name = "cbor-ta", version, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[command(flatten)]
+    inspect: Inspect,
+}
+
+#[derive(Args)]
+struct Inspect {
+    /// CBOR file to read, or `-` for standard input.
+    #[arg(required = true)]
+    file: Option<PathBuf>,
+    /// How many leading values to print per array.
+    #[arg(short = 'n', long = "values", default_value_t = 8)]
+    values: usize,
+    /// Print every value instead of the first few.
+    #[arg(long)]
+    dump: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert CSV/TSV columns into a CBOR map from header to typed array (or
+    /// an array of typed arrays with `--no-headers`).
+    FromCsv {
+        /// Text file to read, or `-` for standard input.
+        file: PathBuf,
+        #[command(flatten)]
+        text: Text,
+        /// Element type of every column, or of one column as `INDEX=TYPE` or
+        /// `NAME=TYPE`; inferred as i64, u64 or f64 otherwise.
+        #[arg(short = 't', long = "type", value_name = "[COLUMN=]TYPE")]
+        types: Vec<String>,
+        /// Byte order of the arrays: `le` or `be`.
+        #[arg(short = 'e', long, default_value = "le")]
+        endianness: Endianness,
+        /// Where to write the CBOR; standard output by default.
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+    },
+    /// Write every typed array in a CBOR file or sequence as a CSV/TSV column
+    /// named by its path.
+    ToCsv {
+        /// CBOR file to read, or `-` for standard input.
+        file: PathBuf,
+        #[command(flatten)]
+        text: Text,
+        /// Where to write the text; standard output by default.
+        #[arg(short = 'o', long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct Text {
+    /// Separate fields with tabs instead of commas.
+    #[arg(long)]
+    tsv: bool,
+    /// The text has no header row.
+    #[arg(long)]
+    no_headers: bool,
+}
+
+impl Text {
+    fn options(&self) -> CsvOptions {
+        CsvOptions {
+            delimiter: if self.tsv { b'\t' } else { b',' },
+            headers: !self.no_headers,
+            ..CsvOptions::default()
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let (file, result) = match cli.command {
+        None => {
+            let file = cli.inspect.file.expect("required by clap");
+            let result = inspect(&file, cli.inspect.values, cli.inspect.dump);
+            (file, result)
+        }
+        Some(Command::FromCsv {
+            file,
+            text,
+            types,
+            endianness,
+            output,
+        }) => {
+            let result = from_csv(&file, &text, &types, endianness, output.as_deref());
+            (file, result)
+        }
+        Some(Command::ToCsv { file, text, output }) => {
+            let result = to_csv(&file, &text, output.as_deref());
+            (file, result)
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("cbor-ta: {}: {e}", file.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read_input(file: &Path) -> io::Result<Vec<u8>> {
+    if file.as_os_str() == "-" {
+        let mut buf = Vec::new();
+        io::stdin().read_to_end(&mut buf)?;
+        Ok(buf)
+    } else {
+        std::fs::read(file)
+    }
+}
+
+fn write_output(output: Option<&Path>, bytes: &[u8]) -> io::Result<()> {
+    match output {
+        Some(path) => std::fs::write(path, bytes),
+        None => io::stdout().lock().write_all(bytes),
+    }
+}
+
+fn inspect(file: &Path, values: usize, dump: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let input = read_input(file)?;
+    let limit = if dump { usize::MAX } else { values };
+    let mut out = io::stdout().lock();
+    walk(&input, &mut |entry| {
+        writeln!(out, "{}", describe(&entry, limit))
+    })?;
+    Ok(())
+}
+
+fn from_csv(
+    file: &Path,
+    text: &Text,
+    types: &[String],
+    endianness: Endianness,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut options = CsvOptions {
+        endianness,
+        ..text.options()
+    };
+    for spec in types {
+        match spec.rsplit_once('=') {
+            None => options.default_type = Some(element_type(spec)?),
+            Some((column, t)) => {
+                let column = match column.parse() {
+                    Ok(index) => ColumnRef::Index(index),
+                    Err(_) => ColumnRef::Name(column.to_string()),
+                };
+                options.types.push((column, element_type(t)?));
+            }
+        }
+    }
+    let columns = read_csv(&read_input(file)?[..], &options)?;
+    let mut buf = Vec::new();
+    let mut e = Encoder::new(&mut buf);
+    if options.headers {
+        e.map(columns.len() as u64)?;
+        for column in &columns {
+            e.str(column.name.as_deref().unwrap_or_default())?
+                .encode(&column.array)?;
+        }
+    } else {
+        e.array(columns.len() as u64)?;
+        for column in &columns {
+            e.encode(&column.array)?;
+        }
+    }
+    write_output(output, &buf)?;
+    Ok(())
+}
+
+fn element_type(name: &str) -> Result<ElementType, Box<dyn std::error::Error>> {
+    name.parse()
+        .map_err(|_| format!("unknown element type {name:?}").into())
+}
+
+fn to_csv(
+    file: &Path,
+    text: &Text,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let input = read_input(file)?;
+    let mut entries = Vec::new();
+    walk(&input, &mut |entry| {
+        entries.push(entry);
+        Ok(())
+    })?;
+    let columns: Vec<_> = entries
+        .iter()
+        .map(|entry| (entry.path.as_str(), &entry.array))
+        .collect();
+    let mut buf = Vec::new();
+    write_csv(&mut buf, &columns, &text.options())?;
+    write_output(output, &buf)?;
+    Ok(())
+}
+
+/// A typed array found in the input, with its shape if it is the payload of a
+/// multi-dimensional array.
+struct Entry<'b> {
+    path: String,
+    shape: Option<Vec<usize>>,
+    array: TypedArrayRef<'b>,
+}
+
+/// Call `found` for every typed array in the CBOR sequence `bytes`.
+///
+/// Paths start with `#<n>` for the n-th item of the sequence, followed by
+/// the array's [`minicbor_typed_arrays::Path`] within that item. Stops at the
+/// first error `found` returns.
+fn walk<'b>(
+    bytes: &'b [u8],
+    found: &mut dyn FnMut(Entry<'b>) -> io::Result<()>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut d = Decoder::new(bytes);
+    let mut item = 0;
+    while d.position() < bytes.len() {
+        for array in TypedArrays::new(&mut d) {
+            let array = array?;
+            found(Entry {
+                path: format!("#{item}{}", array.path),
+                shape: array.shape.map(|(shape, _)| shape),
+                array: array.array,
+            })?;
+        }
+        item += 1;
+    }
+    Ok(())
+}
+
+/// One `path: type endianness len=… min=… max=… mean=…` line, followed by
+/// the first `limit` values.
+fn describe(entry: &Entry<'_>, limit: usize) -> String {
+    let array = &entry.array;
+    let mut line = format!(
+        "{}: {} {}",
+        entry.path,
+        array.element_type().name(),
+        array.endianness().name()
+    );
+    if let Some(shape) = &entry.shape {
+        let _ = write!(line, " shape={shape:?}");
+    }
+    let _ = write!(line, " len={}", array.len());
+    let nan = NanPolicy::Skip;
+    match (array.min(nan), array.max(nan), array.mean(nan)) {
+        (Some(min), Some(max), Some(mean)) => {
+            let _ = write!(line, " min={min} max={max} mean={mean}");
+        }
+        _ => line.push_str(" min=- max=- mean=-"),
+    }
+    line.push_str("\n  [");
+    for (i, element) in array.iter().take(limit).enumerate() {
+        if i > 0 {
+            line.push_str(", ");
+        }
+        let _ = write!(line, "{element}");
+    }
+    if array.len() > limit {
+        if limit > 0 {
+            line.push_str(", ");
+        }
+        let _ = write!(line, "… ({} more)", array.len() - limit);
+    }
+    line.push(']');
+    line
+}
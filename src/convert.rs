@@ -0,0 +1,53 @@
+use crate::element::ElementType;
+#[cfg(feature = "alloc")]
+use crate::element::Scalar;
+use core::fmt;
+
+/// Error converting a typed array to or from another container type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The requested scalar type does not back the array's element type.
+    ElementType {
+        array: ElementType,
+        requested: ElementType,
+    },
+    /// The array's shape does not fit the target container.
+    Shape(&'static str),
+    /// The element at `index` does not fit the requested element type.
+    Value {
+        index: usize,
+        requested: ElementType,
+    },
+}
+
+impl ConvertError {
+    /// Check that `T` backs `array`, as [`ElementType::is_backed_by`] defines.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn check<T: Scalar>(array: ElementType) -> Result<(), ConvertError> {
+        if array.is_backed_by::<T>() {
+            Ok(())
+        } else {
+            Err(ConvertError::ElementType {
+                array,
+                requested: T::ELEMENT_TYPE,
+            })
+        }
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::ElementType { array, requested } => {
+                write!(f, "cannot read {array:?} elements as {requested:?}")
+            }
+            ConvertError::Shape(msg) => write!(f, "shape mismatch: {msg}"),
+            ConvertError::Value { index, requested } => {
+                write!(f, "element {index} does not fit {requested:?}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConvertError {}
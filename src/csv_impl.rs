@@ -0,0 +1,338 @@
+use crate::element::{Element, ElementType, Endianness};
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use std::io;
+
+/// A column of delimited text, by position (from 0) or header name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColumnRef {
+    Index(usize),
+    Name(String),
+}
+
+/// How [`read_csv`] and [`write_csv`] treat delimited text.
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    /// Field separator: `b','` for CSV, `b'\t'` for TSV.
+    pub delimiter: u8,
+    /// Whether the first row holds column names.
+    pub headers: bool,
+    /// Byte order of the arrays [`read_csv`] builds.
+    pub endianness: Endianness,
+    /// Element types of particular columns.
+    pub types: Vec<(ColumnRef, ElementType)>,
+    /// Element type of every other column; `None` infers it.
+    pub default_type: Option<ElementType>,
+}
+
+impl Default for CsvOptions {
+    /// Comma-separated with a header row, little-endian, all types inferred.
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            headers: true,
+            endianness: Endianness::Little,
+            types: Vec::new(),
+            default_type: None,
+        }
+    }
+}
+
+/// One column read by [`read_csv`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvColumn {
+    /// The header, if the input has one.
+    pub name: Option<String>,
+    pub array: OwnedTypedArray,
+}
+
+/// Error converting between delimited text and typed arrays.
+#[derive(Debug)]
+pub enum CsvError {
+    /// The text could not be read or written, or its rows differ in length.
+    Csv(csv::Error),
+    /// A cell is not a valid value of its column's element type.
+    Value {
+        /// 1-based line number, as editors show it.
+        line: u64,
+        /// Column index, from 0 like [`ColumnRef::Index`]; displayed from 1
+        /// to match `line`.
+        column: usize,
+        element_type: ElementType,
+        text: String,
+    },
+    /// A [`ColumnRef`] in [`CsvOptions::types`] names no column.
+    UnknownColumn(ColumnRef),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CsvError::Csv(e) => write!(f, "{e}"),
+            CsvError::Value {
+                line,
+                column,
+                element_type,
+                text,
+            } => write!(
+                f,
+                "line {line}, column {}: {text:?} is not a valid {}",
+                column + 1,
+                element_type.name()
+            ),
+            CsvError::UnknownColumn(ColumnRef::Index(i)) => write!(f, "no column {i}"),
+            CsvError::UnknownColumn(ColumnRef::Name(n)) => write!(f, "no column named {n:?}"),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Csv(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<csv::Error> for CsvError {
+    fn from(e: csv::Error) -> Self {
+        CsvError::Csv(e)
+    }
+}
+
+/// Parse delimited text into one typed array per column.
+///
+/// Each column takes its type from [`CsvOptions::types`], else
+/// [`CsvOptions::default_type`], else the first of `i64`, `u64` and `f64`
+/// that every cell parses as. Cells may be padded with spaces. Empty cells
+/// at the end of a column end it early, as [`write_csv`] pads short columns;
+/// any other empty cell is an error.
+pub fn read_csv<R: io::Read>(reader: R, options: &CsvOptions) -> Result<Vec<CsvColumn>, CsvError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .has_headers(options.headers)
+        .from_reader(reader);
+    let names: Option<Vec<String>> = if options.headers {
+        Some(reader.headers()?.iter().map(ToString::to_string).collect())
+    } else {
+        None
+    };
+    // Cells by column, with the line each row starts on.
+    let mut cells: Vec<Vec<String>> = names.iter().flatten().map(|_| Vec::new()).collect();
+    let mut lines = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        cells.resize_with(record.len(), Vec::new);
+        lines.push(record.position().map_or(0, |p| p.line()));
+        for (column, cell) in cells.iter_mut().zip(&record) {
+            column.push(cell.trim().to_string());
+        }
+    }
+
+    let mut types = alloc::vec![options.default_type; cells.len()];
+    for (column, element_type) in &options.types {
+        let index = match column {
+            ColumnRef::Index(i) => Some(*i).filter(|&i| i < cells.len()),
+            ColumnRef::Name(n) => names.iter().flatten().position(|h| h == n),
+        };
+        let index = index.ok_or_else(|| CsvError::UnknownColumn(column.clone()))?;
+        types[index] = Some(*element_type);
+    }
+
+    let mut columns = Vec::with_capacity(cells.len());
+    for (index, column) in cells.iter().enumerate() {
+        let len = column
+            .iter()
+            .rposition(|c| !c.is_empty())
+            .map_or(0, |i| i + 1);
+        let column = &column[..len];
+        let element_type = types[index].unwrap_or_else(|| infer(column));
+        let width = element_type.width();
+        let mut bytes = alloc::vec![0; column.len() * width];
+        for (row, (text, out)) in column.iter().zip(bytes.chunks_exact_mut(width)).enumerate() {
+            let element = parse(element_type, text).ok_or_else(|| CsvError::Value {
+                line: lines[row],
+                column: index,
+                element_type,
+                text: text.clone(),
+            })?;
+            element.write_into(options.endianness, out);
+        }
+        columns.push(CsvColumn {
+            name: names.as_ref().map(|n| n[index].clone()),
+            array: TypedArray::new(element_type, options.endianness, bytes)
+                .expect("whole elements"),
+        });
+    }
+    Ok(columns)
+}
+
+/// Write typed arrays as the columns of delimited text, named in a header row
+/// if [`CsvOptions::headers`] is set.
+///
+/// Columns shorter than the longest are padded with empty cells. Floats are
+/// written with a decimal point, so [`read_csv`] infers them back as floats.
+pub fn write_csv<W, C>(
+    writer: W,
+    columns: &[(&str, &TypedArray<C>)],
+    options: &CsvOptions,
+) -> Result<(), CsvError>
+where
+    W: io::Write,
+    C: AsRef<[u8]>,
+{
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .from_writer(writer);
+    if options.headers {
+        writer.write_record(columns.iter().map(|(name, _)| name))?;
+    }
+    let mut iters: Vec<_> = columns.iter().map(|(_, array)| array.iter()).collect();
+    let rows = columns.iter().map(|(_, a)| a.len()).max().unwrap_or(0);
+    let mut row = Vec::with_capacity(columns.len());
+    for _ in 0..rows {
+        row.clear();
+        row.extend(
+            iters
+                .iter_mut()
+                .map(|it| it.next().map_or_else(String::new, |e| e.to_string())),
+        );
+        writer.write_record(&row)?;
+    }
+    writer.flush().map_err(|e| CsvError::Csv(e.into()))?;
+    Ok(())
+}
+
+/// The first of `i64`, `u64` and `f64` that every cell parses as, or `f64`
+/// if none fits (so that the error points at a cell).
+fn infer(cells: &[String]) -> ElementType {
+    [ElementType::I64, ElementType::U64]
+        .into_iter()
+        .find(|&t| cells.iter().all(|c| parse(t, c).is_some()))
+        .unwrap_or(ElementType::F64)
+}
+
+fn parse(element_type: ElementType, text: &str) -> Option<Element> {
+    Some(match element_type {
+        ElementType::U8 => Element::U8(text.parse().ok()?),
+        ElementType::U8Clamped => Element::U8Clamped(text.parse().ok()?),
+        ElementType::U16 => Element::U16(text.parse().ok()?),
+        ElementType::U32 => Element::U32(text.parse().ok()?),
+        ElementType::U64 => Element::U64(text.parse().ok()?),
+        ElementType::I8 => Element::I8(text.parse().ok()?),
+        ElementType::I16 => Element::I16(text.parse().ok()?),
+        ElementType::I32 => Element::I32(text.parse().ok()?),
+        ElementType::I64 => Element::I64(text.parse().ok()?),
+        #[cfg(feature = "half")]
+        ElementType::F16 => Element::F16(text.parse().ok()?),
+        #[cfg(feature = "half")]
+        ElementType::Bf16 => Element::Bf16(text.parse().ok()?),
+        ElementType::F32 => Element::F32(text.parse().ok()?),
+        ElementType::F64 => Element::F64(text.parse().ok()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "time,temp,count\n0, 21.5,3\n1,-4,18446744073709551615\n";
+
+    #[test]
+    fn infers_and_overrides_column_types() {
+        let columns = read_csv(TEXT.as_bytes(), &CsvOptions::default()).unwrap();
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_deref()).collect();
+        assert_eq!(names, [Some("time"), Some("temp"), Some("count")]);
+        assert_eq!(columns[0].array.to_vec::<i64>().unwrap(), [0, 1]);
+        assert_eq!(columns[1].array.to_vec::<f64>().unwrap(), [21.5, -4.0]);
+        assert_eq!(columns[2].array.to_vec::<u64>().unwrap(), [3, u64::MAX]);
+
+        let options = CsvOptions {
+            types: alloc::vec![
+                (ColumnRef::Name("temp".into()), ElementType::F32),
+                (ColumnRef::Index(0), ElementType::U8),
+            ],
+            endianness: Endianness::Big,
+            ..CsvOptions::default()
+        };
+        let columns = read_csv(TEXT.as_bytes(), &options).unwrap();
+        assert_eq!(columns[0].array.element_type(), ElementType::U8);
+        assert_eq!(columns[1].array.to_vec::<f32>().unwrap(), [21.5, -4.0]);
+        assert_eq!(columns[1].array.endianness(), Endianness::Big);
+    }
+
+    #[test]
+    fn errors_name_line_and_column() {
+        let options = CsvOptions {
+            default_type: Some(ElementType::U8),
+            ..CsvOptions::default()
+        };
+        let err = read_csv(TEXT.as_bytes(), &options).unwrap_err();
+        assert!(matches!(
+            &err,
+            CsvError::Value { line: 2, column: 1, element_type: ElementType::U8, text }
+                if text == "21.5"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: \"21.5\" is not a valid u8"
+        );
+
+        let err = read_csv("a\n1\nx\n".as_bytes(), &CsvOptions::default()).unwrap_err();
+        assert!(matches!(
+            err,
+            CsvError::Value {
+                line: 3,
+                column: 0,
+                ..
+            }
+        ));
+
+        let options = CsvOptions {
+            types: alloc::vec![(ColumnRef::Name("nope".into()), ElementType::U8)],
+            ..CsvOptions::default()
+        };
+        assert!(matches!(
+            read_csv(TEXT.as_bytes(), &options),
+            Err(CsvError::UnknownColumn(_))
+        ));
+        let ragged = read_csv("a,b\n1\n".as_bytes(), &CsvOptions::default());
+        assert!(matches!(ragged, Err(CsvError::Csv(_))));
+    }
+
+    #[test]
+    fn written_tsv_reads_back() {
+        let x = TypedArray::from_slice(&[1.0f32, 2.5, f32::NAN], Endianness::Little);
+        let n = TypedArray::from_slice(&[-7i16, 8], Endianness::Big);
+        let options = CsvOptions {
+            delimiter: b'\t',
+            ..CsvOptions::default()
+        };
+        let mut text = Vec::new();
+        write_csv(&mut text, &[("x", &x), ("n", &n)], &options).unwrap();
+        assert_eq!(
+            core::str::from_utf8(&text).unwrap(),
+            "x\tn\n1.0\t-7\n2.5\t8\nNaN\t\n"
+        );
+
+        // The short column's padding reads back as its end.
+        let columns = read_csv(&text[..], &options).unwrap();
+        let back = columns[0].array.to_vec::<f64>().unwrap();
+        assert_eq!(back[..2], [1.0, 2.5]);
+        assert!(back[2].is_nan());
+        assert_eq!(columns[1].array.to_vec::<i64>().unwrap(), [-7, 8]);
+
+        let gap = read_csv("a,b\n,1\n2,3\n".as_bytes(), &CsvOptions::default());
+        assert!(matches!(
+            gap,
+            Err(CsvError::Value {
+                line: 2,
+                column: 0,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::element::Element;
+use crate::typed_array::TypedArray;
+use defmt::{Format, Formatter};
+
+/// How many leading values a logged [`TypedArray`] shows.
+const PREFIX: usize = 8;
+
+/// Write the element's value without its type.
+fn format_value(element: Element, f: Formatter<'_>) {
+    match element {
+        Element::U8(v) | Element::U8Clamped(v) => defmt::write!(f, "{=u8}", v),
+        Element::U16(v) => defmt::write!(f, "{=u16}", v),
+        Element::U32(v) => defmt::write!(f, "{=u32}", v),
+        Element::U64(v) => defmt::write!(f, "{=u64}", v),
+        Element::I8(v) => defmt::write!(f, "{=i8}", v),
+        Element::I16(v) => defmt::write!(f, "{=i16}", v),
+        Element::I32(v) => defmt::write!(f, "{=i32}", v),
+        Element::I64(v) => defmt::write!(f, "{=i64}", v),
+        #[cfg(feature = "half")]
+        Element::F16(v) => defmt::write!(f, "{=f32}", v.to_f32()),
+        #[cfg(feature = "half")]
+        Element::Bf16(v) => defmt::write!(f, "{=f32}", v.to_f32()),
+        Element::F32(v) => defmt::write!(f, "{=f32}", v),
+        Element::F64(v) => defmt::write!(f, "{=f64}", v),
+    }
+}
+
+/// Logs as `F32(1.5)`.
+impl Format for Element {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "{}(", self.element_type());
+        format_value(*self, f);
+        defmt::write!(f, ")");
+    }
+}
+
+/// Logs the element type, endianness, length and the first few decoded
+/// values, never the raw payload.
+impl<C: AsRef<[u8]>> Format for TypedArray<C> {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(
+            f,
+            "TypedArray {{ type: {}, endianness: {}, len: {=usize}, values: [",
+            self.element_type(),
+            self.endianness(),
+            self.len()
+        );
+        for (i, element) in self.iter().take(PREFIX).enumerate() {
+            if i > 0 {
+                defmt::write!(f, ", ");
+            }
+            format_value(element, f);
+        }
+        if self.len() > PREFIX {
+            defmt::write!(f, ", …");
+        }
+        defmt::write!(f, "] }}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ElementType, Endianness, OwnedTypedArray};
+
+    fn logged<T: Format>(value: &T) -> alloc::vec::Vec<u8> {
+        defmt::println!("{}", value);
+        defmt::export::fetch_bytes()
+    }
+
+    #[test]
+    fn typed_array_logs_a_bounded_prefix() {
+        let small = TypedArray::from_slice(&[0x5a5a_5a5au32; 4], Endianness::Big);
+        let large = TypedArray::from_slice(&[0x5a5a_5a5au32; 1000], Endianness::Big);
+        let count = |bytes: &[u8]| bytes.windows(4).filter(|w| w == &[0x5a; 4]).count();
+        assert_eq!(count(&logged(&small)), 4);
+        let bytes = logged(&large);
+        assert_eq!(count(&bytes), PREFIX);
+        assert!(bytes.len() < 1000);
+    }
+
+    #[test]
+    fn elements_and_metadata_log() {
+        assert!(!logged(&Element::I16(-2)).is_empty());
+        assert!(!logged(&ElementType::F64).is_empty());
+        assert!(!logged(&Endianness::Little).is_empty());
+        let empty = OwnedTypedArray::from_slice::<f32>(&[], Endianness::Little);
+        assert!(!logged(&empty).is_empty());
+    }
+}
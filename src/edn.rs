@@ -0,0 +1,279 @@
+#[cfg(feature = "half")]
+use crate::element::ElementType;
+#[cfg(feature = "half")]
+use crate::tag::BF16_MARKER_TAG;
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write as _};
+use minicbor::data::Tag;
+use minicbor::Encoder;
+
+// RFC 8949 §8 diagnostic notation, the subset a typed array needs: tag
+// numbers, `h'…'` byte strings and `/…/` comments.
+
+/// Error parsing a typed array from diagnostic notation.
+#[derive(Debug)]
+pub enum EdnError {
+    /// The text is not `tag(h'…')` at byte `offset`.
+    Syntax {
+        offset: usize,
+        expected: &'static str,
+    },
+    /// The tags and bytes do not form a typed array.
+    Decode(minicbor::decode::Error),
+}
+
+impl fmt::Display for EdnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EdnError::Syntax { offset, expected } => {
+                write!(f, "expected {expected} at byte {offset}")
+            }
+            EdnError::Decode(e) => write!(f, "not a typed array: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EdnError {}
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// Diagnostic notation, e.g. `85(h'0000803f00000040')`.
+    ///
+    /// `Bf16` arrays are written with [`crate::Bf16Encoding::default`]'s
+    /// marker tag around the `u16` tag.
+    pub fn to_edn(&self) -> String {
+        let mut out = String::new();
+        self.write_edn(&mut out);
+        out
+    }
+
+    /// [`TypedArray::to_edn`] between comments naming the type and listing
+    /// the values, e.g. `/f32le/ 85(h'0000803f00000040') / [1.0, 2.0] /`.
+    pub fn to_edn_annotated(&self) -> String {
+        let mut out = String::new();
+        let _ = write!(out, "/{}", self.element_type().name());
+        if self.element_type().width() > 1 {
+            out.push_str(self.endianness().name());
+        }
+        out.push_str("/ ");
+        self.write_edn(&mut out);
+        out.push_str(" / [");
+        for (i, element) in self.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            let _ = write!(out, "{element}");
+        }
+        out.push_str("] /");
+        out
+    }
+
+    fn write_edn(&self, out: &mut String) {
+        let marker = match self.element_type() {
+            #[cfg(feature = "half")]
+            ElementType::Bf16 => Some(BF16_MARKER_TAG),
+            _ => None,
+        };
+        let tag = self.element_type().tag(self.endianness()).tag().as_u64();
+        for tag in marker.into_iter().chain([tag]) {
+            let _ = write!(out, "{tag}(");
+        }
+        out.push_str("h'");
+        for byte in self.as_bytes() {
+            let _ = write!(out, "{byte:02x}");
+        }
+        out.push('\'');
+        out.push(')');
+        if marker.is_some() {
+            out.push(')');
+        }
+    }
+}
+
+impl OwnedTypedArray {
+    /// Parse the output of [`TypedArray::to_edn`] or
+    /// [`TypedArray::to_edn_annotated`].
+    ///
+    /// Comments may appear anywhere whitespace may, and the hex digits may be
+    /// split by whitespace.
+    pub fn from_edn(text: &str) -> Result<Self, EdnError> {
+        let mut p = Parser { text, pos: 0 };
+        let mut tags = Vec::new();
+        p.skip_blank()?;
+        while p.peek().is_some_and(|c| c.is_ascii_digit()) {
+            tags.push(p.number()?);
+            p.skip_blank()?;
+            p.expect("(", "`(`")?;
+            p.skip_blank()?;
+        }
+        if tags.is_empty() {
+            return Err(p.error("a tag number"));
+        }
+        let bytes = p.hex()?;
+        for _ in &tags {
+            p.skip_blank()?;
+            p.expect(")", "`)`")?;
+        }
+        p.skip_blank()?;
+        if p.pos != text.len() {
+            return Err(p.error("end of input"));
+        }
+
+        let mut cbor = Vec::new();
+        let mut e = Encoder::new(&mut cbor);
+        for tag in tags {
+            e.tag(Tag::new(tag)).expect("writing to a Vec");
+        }
+        e.bytes(&bytes).expect("writing to a Vec");
+        minicbor::decode(&cbor).map_err(EdnError::Decode)
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, expected: &'static str) -> EdnError {
+        EdnError::Syntax {
+            offset: self.pos,
+            expected,
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn expect(&mut self, token: &str, expected: &'static str) -> Result<(), EdnError> {
+        if self.text[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            Ok(())
+        } else {
+            Err(self.error(expected))
+        }
+    }
+
+    /// Skip whitespace and `/…/` comments.
+    fn skip_blank(&mut self) -> Result<(), EdnError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_whitespace() => self.pos += 1,
+                Some(b'/') => match self.text[self.pos + 1..].find('/') {
+                    Some(len) => self.pos += len + 2,
+                    None => return Err(self.error("`/` closing a comment")),
+                },
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<u64, EdnError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        self.text[start..self.pos]
+            .parse()
+            .map_err(|_| EdnError::Syntax {
+                offset: start,
+                expected: "a tag number below 2^64",
+            })
+    }
+
+    /// An `h'…'` byte string.
+    fn hex(&mut self) -> Result<Vec<u8>, EdnError> {
+        self.expect("h'", "`h'`")?;
+        let mut bytes = Vec::new();
+        let mut high = None;
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("`'` closing the byte string"));
+            };
+            let nibble = match c {
+                b'\'' => break,
+                c if c.is_ascii_whitespace() => {
+                    self.pos += 1;
+                    continue;
+                }
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => return Err(self.error("a hex digit")),
+            };
+            self.pos += 1;
+            match high.take() {
+                None => high = Some(nibble),
+                Some(h) => bytes.push(h << 4 | nibble),
+            }
+        }
+        if high.is_some() {
+            return Err(self.error("an even number of hex digits"));
+        }
+        self.pos += 1;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ElementType, Endianness};
+
+    #[test]
+    fn formats_plain_and_annotated() {
+        let a = TypedArray::from_slice(&[1.0f32, 2.0], Endianness::Little);
+        assert_eq!(a.to_edn(), "85(h'0000803f00000040')");
+        assert_eq!(
+            a.to_edn_annotated(),
+            "/f32le/ 85(h'0000803f00000040') / [1.0, 2.0] /"
+        );
+        let b = TypedArray::from_slice(&[-1i8], Endianness::Big);
+        assert_eq!(b.to_edn_annotated(), "/i8/ 72(h'ff') / [-1] /");
+    }
+
+    #[test]
+    fn parses_both_forms_back() {
+        let a = TypedArray::from_slice(&[7u16, 0x1234, u16::MAX], Endianness::Big);
+        assert_eq!(OwnedTypedArray::from_edn(&a.to_edn()).unwrap(), a);
+        assert_eq!(OwnedTypedArray::from_edn(&a.to_edn_annotated()).unwrap(), a);
+        let spaced = " /x/ 65 ( h'0007 1234\n FFFF' ) /y/ ";
+        assert_eq!(OwnedTypedArray::from_edn(spaced).unwrap(), a);
+        let empty = OwnedTypedArray::from_edn("86(h'')").unwrap();
+        assert_eq!(empty.element_type(), ElementType::F64);
+        assert!(empty.is_empty());
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn bf16_keeps_its_marker() {
+        let a = TypedArray::from_slice(&[half::bf16::from_f32(1.5)], Endianness::Little);
+        assert_eq!(a.to_edn(), "1650864438(69(h'c03f'))");
+        assert_eq!(OwnedTypedArray::from_edn(&a.to_edn()).unwrap(), a);
+    }
+
+    #[test]
+    fn rejects_malformed_text() {
+        let syntax = |text| match OwnedTypedArray::from_edn(text) {
+            Err(EdnError::Syntax { offset, .. }) => offset,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(syntax("h'00'"), 0);
+        assert_eq!(syntax("64(h'0g')"), 6);
+        assert_eq!(syntax("64(h'000')"), 8);
+        assert_eq!(syntax("64(h'00'"), 8);
+        assert_eq!(syntax("64(h'00') 1"), 10);
+        assert_eq!(syntax("/open 64(h'00')"), 0);
+        assert!(matches!(
+            OwnedTypedArray::from_edn("24(h'00')"),
+            Err(EdnError::Decode(_))
+        ));
+        assert!(matches!(
+            OwnedTypedArray::from_edn("69(h'000000')"),
+            Err(EdnError::Decode(_))
+        ));
+    }
+}
@@ -1,24 +1,63 @@
+use core::fmt;
+use core::str::FromStr;
+
 /// Byte order of a typed array's elements.
 ///
 /// For single-byte element types (`U8`, `U8Clamped`, `I8`) endianness is
 /// meaningless; [`crate::TypedArray::new`] canonicalizes those to
 /// [`Endianness::Big`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Endianness {
+    #[cfg_attr(feature = "serde", serde(rename = "be"))]
     Big,
+    #[cfg_attr(feature = "serde", serde(rename = "le"))]
     Little,
 }
 
+impl Endianness {
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endianness = Endianness::Big;
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endianness = Endianness::Little;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
 /// A numeric scalar that can back a typed array element.
 ///
 /// Implemented for every primitive RFC8746 element type plus [`half::f16`]
-/// (under the `half` feature). Public so that [`crate::TypedArray::from_slice`]
-/// can be generic over it.
-pub trait Scalar: Copy {
+/// and [`half::bf16`] (under the `half` feature). Public so that [`crate::TypedArray::from_slice`]
+/// can be generic over it. Sealed: every implementor is plain old data valid
+/// for any bit pattern, which zero-copy views such as
+/// [`crate::TypedArray::as_native_slice`] rely on.
+pub trait Scalar: Copy + sealed::Sealed {
     /// The [`ElementType`] discriminant for this scalar.
     const ELEMENT_TYPE: ElementType;
     fn to_f64(self) -> f64;
     fn to_i64(self) -> i64;
+    /// Write this value's big-endian bytes into `out`.
+    ///
+    /// `out.len()` must equal the element width.
+    fn write_be_into(self, out: &mut [u8]);
+    /// Write this value's little-endian bytes into `out`.
+    ///
+    /// `out.len()` must equal the element width.
+    fn write_le_into(self, out: &mut [u8]);
+    /// Read a value from its big-endian bytes.
+    ///
+    /// `bytes.len()` must equal the element width.
+    fn read_be(bytes: &[u8]) -> Self;
+    /// Read a value from its little-endian bytes.
+    ///
+    /// `bytes.len()` must equal the element width.
+    fn read_le(bytes: &[u8]) -> Self;
     #[cfg(feature = "alloc")]
     fn write_be(self, out: &mut alloc::vec::Vec<u8>);
     #[cfg(feature = "alloc")]
@@ -28,10 +67,24 @@ pub trait Scalar: Copy {
 macro_rules! impl_scalar {
     ( $( $ty:ty => $et:expr ),+ $(,)? ) => {
         $(
+            impl sealed::Sealed for $ty {}
+
             impl Scalar for $ty {
                 const ELEMENT_TYPE: ElementType = $et;
                 fn to_f64(self) -> f64 { self as f64 }
                 fn to_i64(self) -> i64 { self as i64 }
+                fn write_be_into(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_be_bytes());
+                }
+                fn write_le_into(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+                fn read_be(bytes: &[u8]) -> Self {
+                    <$ty>::from_be_bytes(bytes.try_into().expect("bytes length must equal element width"))
+                }
+                fn read_le(bytes: &[u8]) -> Self {
+                    <$ty>::from_le_bytes(bytes.try_into().expect("bytes length must equal element width"))
+                }
                 #[cfg(feature = "alloc")]
                 fn write_be(self, out: &mut alloc::vec::Vec<u8>) {
                     out.extend_from_slice(&self.to_be_bytes());
@@ -58,25 +111,59 @@ impl_scalar!(
     f64 => ElementType::F64,
 );
 
-#[cfg(feature = "half")]
-impl Scalar for half::f16 {
-    const ELEMENT_TYPE: ElementType = ElementType::F16;
-    fn to_f64(self) -> f64 {
-        f64::from(self)
-    }
-    fn to_i64(self) -> i64 {
-        f64::from(self) as i64
-    }
-    #[cfg(feature = "alloc")]
-    fn write_be(self, out: &mut alloc::vec::Vec<u8>) {
-        out.extend_from_slice(&self.to_be_bytes());
-    }
-    #[cfg(feature = "alloc")]
-    fn write_le(self, out: &mut alloc::vec::Vec<u8>) {
-        out.extend_from_slice(&self.to_le_bytes());
-    }
+macro_rules! impl_half_scalar {
+    ( $( $ty:ty => $et:expr ),+ $(,)? ) => {
+        $(
+            #[cfg(feature = "half")]
+            impl sealed::Sealed for $ty {}
+
+            #[cfg(feature = "half")]
+            impl Scalar for $ty {
+                const ELEMENT_TYPE: ElementType = $et;
+                fn to_f64(self) -> f64 {
+                    f64::from(self)
+                }
+                fn to_i64(self) -> i64 {
+                    f64::from(self) as i64
+                }
+                fn write_be_into(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_be_bytes());
+                }
+                fn write_le_into(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_le_bytes());
+                }
+                fn read_be(bytes: &[u8]) -> Self {
+                    <$ty>::from_be_bytes(
+                        bytes
+                            .try_into()
+                            .expect("bytes length must equal element width"),
+                    )
+                }
+                fn read_le(bytes: &[u8]) -> Self {
+                    <$ty>::from_le_bytes(
+                        bytes
+                            .try_into()
+                            .expect("bytes length must equal element width"),
+                    )
+                }
+                #[cfg(feature = "alloc")]
+                fn write_be(self, out: &mut alloc::vec::Vec<u8>) {
+                    out.extend_from_slice(&self.to_be_bytes());
+                }
+                #[cfg(feature = "alloc")]
+                fn write_le(self, out: &mut alloc::vec::Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+        )+
+    };
 }
 
+impl_half_scalar!(
+    half::f16  => ElementType::F16,
+    half::bf16 => ElementType::Bf16,
+);
+
 macro_rules! define_elements {
     (
         $(
@@ -86,17 +173,28 @@ macro_rules! define_elements {
     ) => {
         /// The element type of a typed array (value-less descriptor).
         #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
         pub enum ElementType {
             $( $(#[$meta])* $variant, )+
         }
 
         /// A single decoded typed-array element.
         #[derive(Clone, Copy, Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
         pub enum Element {
             $( $(#[$meta])* $variant($ty), )+
         }
 
         impl ElementType {
+            /// Every supported element type.
+            pub const ALL: &'static [ElementType] = &[
+                $( $(#[$meta])* ElementType::$variant, )+
+            ];
+
             /// Width, in bytes, of one element of this type.
             pub const fn width(self) -> usize {
                 match self {
@@ -107,6 +205,13 @@ macro_rules! define_elements {
             /// The IANA tag for this element type in the given endianness.
             ///
             /// Single-byte types ignore `endianness` (both map to the same tag).
+            ///
+            /// `Bf16` has no RFC8746 tag and returns the `u16` tag of its
+            /// payload, so writing only this tag in front of a `bf16` payload
+            /// produces a `u16` array: the [`crate::Bf16Encoding`] marker is
+            /// lost. Encode arrays with the `Encode` impl,
+            /// [`crate::TypedArray::encode_with`] or [`crate::encode_iter`],
+            /// which write the marker too.
             pub fn tag(self, endianness: Endianness) -> minicbor::data::IanaTag {
                 match (self, endianness) {
                     $(
@@ -141,6 +246,28 @@ macro_rules! define_elements {
         }
 
         impl Element {
+            /// The [`ElementType`] of this element.
+            pub fn element_type(self) -> ElementType {
+                match self {
+                    $( $(#[$meta])* Element::$variant(_) => ElementType::$variant, )+
+                }
+            }
+
+            /// Write this element's bytes in `endianness` into `out`.
+            ///
+            /// `out.len()` must equal the element width.
+            #[cfg(feature = "alloc")]
+            pub(crate) fn write_into(self, endianness: Endianness, out: &mut [u8]) {
+                match (self, endianness) {
+                    $(
+                        $(#[$meta])*
+                        (Element::$variant(v), Endianness::Big) => v.write_be_into(out),
+                        $(#[$meta])*
+                        (Element::$variant(v), Endianness::Little) => v.write_le_into(out),
+                    )+
+                }
+            }
+
             /// Lossy conversion of this element's value to `f64`.
             pub fn to_f64(self) -> f64 {
                 match self {
@@ -170,10 +297,226 @@ define_elements! {
     I64       => i64,        minicbor::data::IanaTag::TypedArrayI64B,      minicbor::data::IanaTag::TypedArrayI64L;
     #[cfg(feature = "half")]
     F16       => half::f16,  minicbor::data::IanaTag::TypedArrayF16B,      minicbor::data::IanaTag::TypedArrayF16L;
+    #[cfg(feature = "half")]
+    Bf16      => half::bf16, minicbor::data::IanaTag::TypedArrayU16B,      minicbor::data::IanaTag::TypedArrayU16L;
     F32       => f32,        minicbor::data::IanaTag::TypedArrayF32B,      minicbor::data::IanaTag::TypedArrayF32L;
     F64       => f64,        minicbor::data::IanaTag::TypedArrayF64B,      minicbor::data::IanaTag::TypedArrayF64L;
 }
 
+/// Largest [`ElementType::width`] of any supported element type.
+pub(crate) const MAX_WIDTH: usize = 8;
+
+impl ElementType {
+    /// Whether values of the scalar `T` can back arrays of this type.
+    ///
+    /// True when `T::ELEMENT_TYPE` is this type, and additionally for `u8`
+    /// against [`ElementType::U8Clamped`], which shares its layout.
+    pub(crate) fn is_backed_by<T: Scalar>(self) -> bool {
+        T::ELEMENT_TYPE == self
+            || (T::ELEMENT_TYPE == ElementType::U8 && self == ElementType::U8Clamped)
+    }
+
+    /// Lowercase name, as in `"f32"` or `"u8clamped"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            ElementType::U8 => "u8",
+            ElementType::U8Clamped => "u8clamped",
+            ElementType::U16 => "u16",
+            ElementType::U32 => "u32",
+            ElementType::U64 => "u64",
+            ElementType::I8 => "i8",
+            ElementType::I16 => "i16",
+            ElementType::I32 => "i32",
+            ElementType::I64 => "i64",
+            #[cfg(feature = "half")]
+            ElementType::F16 => "f16",
+            #[cfg(feature = "half")]
+            ElementType::Bf16 => "bf16",
+            ElementType::F32 => "f32",
+            ElementType::F64 => "f64",
+        }
+    }
+}
+
+impl Endianness {
+    /// `"be"` or `"le"`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Endianness::Big => "be",
+            Endianness::Little => "le",
+        }
+    }
+}
+
+/// An element's value, widened so that every element type fits exactly
+/// (integers) or as closely as `f64` allows (floats).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Value {
+    Int(i128),
+    Float(f64),
+}
+
+impl Element {
+    /// This element's value as an element of type `to`, or `None` if it does
+    /// not fit.
+    ///
+    /// Integer targets take integers in range and integral floats in range.
+    /// Float targets round to nearest. [`ElementType::U8Clamped`] clamps to
+    /// 0–255, rounding floats half to even, with NaN becoming 0.
+    pub fn convert(self, to: ElementType) -> Option<Element> {
+        self.value().to_element(to)
+    }
+
+    pub(crate) fn value(self) -> Value {
+        match self {
+            Element::U8(v) | Element::U8Clamped(v) => Value::Int(v.into()),
+            Element::U16(v) => Value::Int(v.into()),
+            Element::U32(v) => Value::Int(v.into()),
+            Element::U64(v) => Value::Int(v.into()),
+            Element::I8(v) => Value::Int(v.into()),
+            Element::I16(v) => Value::Int(v.into()),
+            Element::I32(v) => Value::Int(v.into()),
+            Element::I64(v) => Value::Int(v.into()),
+            #[cfg(feature = "half")]
+            Element::F16(v) => Value::Float(v.to_f64()),
+            #[cfg(feature = "half")]
+            Element::Bf16(v) => Value::Float(v.to_f64()),
+            Element::F32(v) => Value::Float(v.into()),
+            Element::F64(v) => Value::Float(v),
+        }
+    }
+}
+
+impl Value {
+    /// The value as an element of type `to`, as [`Element::convert`] defines.
+    pub(crate) fn to_element(self, to: ElementType) -> Option<Element> {
+        let int = || match self {
+            Value::Int(v) => Some(v),
+            // Saturates out of range and maps NaN to 0, neither of which
+            // survives the round trip.
+            Value::Float(v) => Some(v as i128).filter(|&i| i as f64 == v),
+        };
+        Some(match to {
+            ElementType::U8 => Element::U8(int()?.try_into().ok()?),
+            ElementType::U8Clamped => Element::U8Clamped(match self {
+                Value::Int(v) => v.clamp(0, 255) as u8,
+                Value::Float(v) => clamp_u8(v),
+            }),
+            ElementType::U16 => Element::U16(int()?.try_into().ok()?),
+            ElementType::U32 => Element::U32(int()?.try_into().ok()?),
+            ElementType::U64 => Element::U64(int()?.try_into().ok()?),
+            ElementType::I8 => Element::I8(int()?.try_into().ok()?),
+            ElementType::I16 => Element::I16(int()?.try_into().ok()?),
+            ElementType::I32 => Element::I32(int()?.try_into().ok()?),
+            ElementType::I64 => Element::I64(int()?.try_into().ok()?),
+            #[cfg(feature = "half")]
+            ElementType::F16 => Element::F16(half::f16::from_f64(self.to_f64())),
+            #[cfg(feature = "half")]
+            ElementType::Bf16 => Element::Bf16(half::bf16::from_f64(self.to_f64())),
+            ElementType::F32 => Element::F32(match self {
+                Value::Int(v) => v as f32,
+                Value::Float(v) => v as f32,
+            }),
+            ElementType::F64 => Element::F64(self.to_f64()),
+        })
+    }
+
+    pub(crate) fn to_f64(self) -> f64 {
+        match self {
+            Value::Int(v) => v as f64,
+            Value::Float(v) => v,
+        }
+    }
+}
+
+/// Clamp to 0–255 and round half to even, as `Uint8ClampedArray` does.
+fn clamp_u8(v: f64) -> u8 {
+    if v.is_nan() {
+        return 0;
+    }
+    let v = v.clamp(0.0, 255.0);
+    let floor = v as u8;
+    let rest = v - f64::from(floor);
+    if rest > 0.5 || (rest == 0.5 && floor % 2 == 1) {
+        floor + 1
+    } else {
+        floor
+    }
+}
+
+/// Shows the bare value, as in `-3` or `2.5`.
+///
+/// Floats keep a decimal point (`1.0`) unless a precision is given, which
+/// they then honour; width, fill and alignment apply to every type.
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        macro_rules! float {
+            ($v:expr) => {
+                if f.precision().is_some() {
+                    fmt::Display::fmt(&$v, f)
+                } else {
+                    fmt::Debug::fmt(&$v, f)
+                }
+            };
+        }
+        match *self {
+            Element::U8(v) | Element::U8Clamped(v) => fmt::Display::fmt(&v, f),
+            Element::U16(v) => fmt::Display::fmt(&v, f),
+            Element::U32(v) => fmt::Display::fmt(&v, f),
+            Element::U64(v) => fmt::Display::fmt(&v, f),
+            Element::I8(v) => fmt::Display::fmt(&v, f),
+            Element::I16(v) => fmt::Display::fmt(&v, f),
+            Element::I32(v) => fmt::Display::fmt(&v, f),
+            Element::I64(v) => fmt::Display::fmt(&v, f),
+            #[cfg(feature = "half")]
+            Element::F16(v) => float!(v.to_f32()),
+            #[cfg(feature = "half")]
+            Element::Bf16(v) => float!(v.to_f32()),
+            Element::F32(v) => float!(v),
+            Element::F64(v) => float!(v),
+        }
+    }
+}
+
+/// Error parsing an [`ElementType`] or [`Endianness`] from its name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownName;
+
+impl fmt::Display for UnknownName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown element type or endianness name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownName {}
+
+/// Parses the [`ElementType::name`] form.
+impl FromStr for ElementType {
+    type Err = UnknownName;
+
+    fn from_str(s: &str) -> Result<Self, UnknownName> {
+        ElementType::ALL
+            .iter()
+            .copied()
+            .find(|t| t.name() == s)
+            .ok_or(UnknownName)
+    }
+}
+
+/// Parses `"be"`/`"le"`, or `"big"`/`"little"`.
+impl FromStr for Endianness {
+    type Err = UnknownName;
+
+    fn from_str(s: &str) -> Result<Self, UnknownName> {
+        match s {
+            "be" | "big" => Ok(Endianness::Big),
+            "le" | "little" => Ok(Endianness::Little),
+            _ => Err(UnknownName),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +541,28 @@ mod tests {
         assert_eq!(le, Element::U16(0x3412));
     }
 
+    #[test]
+    fn elements_display_bare_values() {
+        use std::format;
+        assert_eq!(format!("{}", Element::I16(-3)), "-3");
+        assert_eq!(format!("{}", Element::F32(1.0)), "1.0");
+        assert_eq!(format!("{}", Element::F64(0.1)), "0.1");
+        assert_eq!(format!("{:.2}", Element::F32(2.5)), "2.50");
+        assert_eq!(format!("{:>5}", Element::U8(7)), "    7");
+        assert_eq!(format!("{:<6}|", Element::F64(-0.5)), "-0.5  |");
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for &t in ElementType::ALL {
+            assert_eq!(t.name().parse(), Ok(t));
+        }
+        assert_eq!(ElementType::U8Clamped.name(), "u8clamped");
+        assert_eq!("little".parse(), Ok(Endianness::Little));
+        assert_eq!(Endianness::Big.name().parse(), Ok(Endianness::Big));
+        assert_eq!("f128".parse::<ElementType>(), Err(UnknownName));
+    }
+
     #[test]
     fn tag_maps_endianness() {
         use minicbor::data::IanaTag;
@@ -220,6 +585,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn max_width_bounds_every_type() {
+        assert!(ElementType::ALL.iter().all(|t| t.width() <= MAX_WIDTH));
+        assert!(ElementType::ALL.iter().any(|t| t.width() == MAX_WIDTH));
+    }
+
     #[test]
     fn element_value_conversions() {
         assert_eq!(Element::I16(-5).to_i64(), -5);
@@ -228,6 +599,38 @@ mod tests {
         assert_eq!(Element::F32(2.0).to_i64(), 2);
     }
 
+    #[test]
+    fn convert_keeps_values_or_refuses() {
+        assert_eq!(
+            Element::I16(-5).convert(ElementType::I64),
+            Some(Element::I64(-5))
+        );
+        assert_eq!(Element::I16(-5).convert(ElementType::U32), None);
+        assert_eq!(Element::U64(u64::MAX).convert(ElementType::I64), None);
+        assert_eq!(
+            Element::F64(3.0).convert(ElementType::U8),
+            Some(Element::U8(3))
+        );
+        assert_eq!(Element::F32(2.5).convert(ElementType::I32), None);
+        assert_eq!(Element::F64(f64::NAN).convert(ElementType::I8), None);
+        assert_eq!(Element::F64(1e300).convert(ElementType::U64), None);
+        assert_eq!(
+            Element::U64(u64::MAX).convert(ElementType::F32),
+            Some(Element::F32(1.8446744e19))
+        );
+        assert_eq!(
+            Element::F64(0.1).convert(ElementType::F32),
+            Some(Element::F32(0.1))
+        );
+
+        let clamped = |e: Element| e.convert(ElementType::U8Clamped);
+        assert_eq!(clamped(Element::I32(-7)), Some(Element::U8Clamped(0)));
+        assert_eq!(clamped(Element::U16(300)), Some(Element::U8Clamped(255)));
+        assert_eq!(clamped(Element::F64(2.5)), Some(Element::U8Clamped(2)));
+        assert_eq!(clamped(Element::F64(3.5)), Some(Element::U8Clamped(4)));
+        assert_eq!(clamped(Element::F32(f32::NAN)), Some(Element::U8Clamped(0)));
+    }
+
     #[cfg(feature = "half")]
     #[test]
     fn f16_conversions() {
@@ -235,5 +638,11 @@ mod tests {
         assert_eq!(Element::F16(v).to_f64(), 3.5);
         let decoded = ElementType::F16.decode_chunk(&v.to_le_bytes(), Endianness::Little);
         assert_eq!(decoded, Element::F16(v));
+
+        let b = half::bf16::from_f32(-0.75);
+        assert_eq!(ElementType::Bf16.width(), 2);
+        assert_eq!(Element::Bf16(b).to_f64(), -0.75);
+        let decoded = ElementType::Bf16.decode_chunk(&b.to_be_bytes(), Endianness::Big);
+        assert_eq!(decoded, Element::Bf16(b));
     }
 }
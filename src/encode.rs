@@ -0,0 +1,120 @@
+use crate::element::{ElementType, Endianness, Scalar, MAX_WIDTH};
+use crate::tag::{encode_tag, Bf16Encoding};
+use minicbor::encode::{Error, Write};
+use minicbor::Encoder;
+
+/// Encode a typed array from an iterator of known length, without buffering.
+///
+/// Writes the RFC8746 tag for `element_type`/`endianness` and a byte-string
+/// header for `len` elements, then encodes each value yielded by `values`
+/// straight to the encoder's writer. Works without an allocator.
+///
+/// `T` must back `element_type` (`u8` may also back
+/// [`ElementType::U8Clamped`]). Returns an error if it does not, or if
+/// `values` yields more or fewer than `len` items; in the latter case the
+/// header and some elements have already been written.
+pub fn encode_iter<T, I, W>(
+    e: &mut Encoder<W>,
+    element_type: ElementType,
+    endianness: Endianness,
+    len: usize,
+    values: I,
+) -> Result<(), Error<W::Error>>
+where
+    T: Scalar,
+    I: IntoIterator<Item = T>,
+    W: Write,
+{
+    if !element_type.is_backed_by::<T>() {
+        return Err(Error::message("scalar type does not match element type"));
+    }
+    let width = element_type.width();
+    let byte_len = len
+        .checked_mul(width)
+        .ok_or_else(|| Error::message("typed array byte length overflows"))?;
+    encode_tag(e, element_type, endianness, Bf16Encoding::default())?;
+    e.bytes_len(byte_len as u64)?;
+
+    let mut buf = [0u8; MAX_WIDTH];
+    let chunk = &mut buf[..width];
+    let mut values = values.into_iter();
+    for _ in 0..len {
+        let v = values
+            .next()
+            .ok_or_else(|| Error::message("iterator yielded fewer items than len"))?;
+        match endianness {
+            Endianness::Big => v.write_be_into(chunk),
+            Endianness::Little => v.write_le_into(chunk),
+        }
+        e.writer_mut().write_all(chunk).map_err(Error::write)?;
+    }
+    if values.next().is_some() {
+        return Err(Error::message("iterator yielded more items than len"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OwnedTypedArray, TypedArray};
+
+    fn encode_slice<T: Scalar>(values: &[T], endianness: Endianness) -> alloc::vec::Vec<u8> {
+        let mut buf = alloc::vec::Vec::new();
+        let mut e = Encoder::new(&mut buf);
+        encode_iter(
+            &mut e,
+            T::ELEMENT_TYPE,
+            endianness,
+            values.len(),
+            values.iter().copied(),
+        )
+        .unwrap();
+        buf
+    }
+
+    #[test]
+    fn matches_from_slice_encoding() {
+        for end in [Endianness::Big, Endianness::Little] {
+            let values = [1.5f32, -2.0, 3.25];
+            let mut expected = alloc::vec::Vec::new();
+            minicbor::encode(TypedArray::from_slice(&values, end), &mut expected).unwrap();
+            assert_eq!(encode_slice(&values, end), expected);
+        }
+    }
+
+    #[test]
+    fn decodes_back() {
+        let buf = encode_slice(&[7u64, 8, 9], Endianness::Little);
+        let decoded: OwnedTypedArray = minicbor::decode(&buf).unwrap();
+        assert_eq!(
+            decoded,
+            TypedArray::from_slice(&[7u64, 8, 9], Endianness::Little)
+        );
+    }
+
+    #[test]
+    fn u8_backs_u8_clamped() {
+        let mut buf = alloc::vec::Vec::new();
+        let mut e = Encoder::new(&mut buf);
+        encode_iter(&mut e, ElementType::U8Clamped, Endianness::Big, 2, [1u8, 2]).unwrap();
+        let decoded: OwnedTypedArray = minicbor::decode(&buf).unwrap();
+        assert_eq!(decoded.element_type(), ElementType::U8Clamped);
+    }
+
+    #[test]
+    fn rejects_wrong_item_count() {
+        let mut buf = alloc::vec::Vec::new();
+        let mut e = Encoder::new(&mut buf);
+        assert!(encode_iter(&mut e, ElementType::U16, Endianness::Big, 3, [1u16, 2]).is_err());
+        let mut e = Encoder::new(&mut buf);
+        assert!(encode_iter(&mut e, ElementType::U16, Endianness::Big, 1, [1u16, 2]).is_err());
+    }
+
+    #[test]
+    fn rejects_mismatched_scalar() {
+        let mut buf = alloc::vec::Vec::new();
+        let mut e = Encoder::new(&mut buf);
+        assert!(encode_iter(&mut e, ElementType::I16, Endianness::Big, 1, [1u16]).is_err());
+    }
+}
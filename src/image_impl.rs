@@ -0,0 +1,172 @@
+use crate::convert::ConvertError;
+use crate::element::{ElementType, Endianness, Scalar};
+use crate::multi_dim::{MultiDimArray, Order, OwnedMultiDimArray};
+use crate::typed_array::TypedArray;
+use alloc::vec::Vec;
+use core::ops::Deref;
+use image::{ImageBuffer, Pixel};
+
+/// The image's `[height, width, channels]` shape and its samples.
+///
+/// `ImageBuffer::from_raw` accepts containers longer than the image, so the
+/// samples are cut to the shape rather than taken as the whole container.
+fn image_samples<P, B>(image: &ImageBuffer<P, B>) -> (Vec<usize>, &[P::Subpixel])
+where
+    P: Pixel,
+    B: Deref<Target = [P::Subpixel]>,
+{
+    let (width, height) = image.dimensions();
+    let shape = alloc::vec![
+        height as usize,
+        width as usize,
+        usize::from(P::CHANNEL_COUNT)
+    ];
+    let len = shape.iter().product();
+    (shape, &image.as_raw()[..len])
+}
+
+impl OwnedMultiDimArray {
+    /// Build a row-major (tag 40) `[height, width, channels]` array from an
+    /// `image` buffer, such as `GrayImage`, `RgbImage`, `ImageBuffer<Rgba<u16>, _>`
+    /// or `Rgb32FImage`.
+    pub fn from_image<P, B>(image: &ImageBuffer<P, B>, endianness: Endianness) -> Self
+    where
+        P: Pixel,
+        P::Subpixel: Scalar,
+        B: Deref<Target = [P::Subpixel]>,
+    {
+        let (shape, samples) = image_samples(image);
+        MultiDimArray::new(
+            shape,
+            Order::RowMajor,
+            TypedArray::from_slice(samples, endianness),
+        )
+        .expect("image shape matches its sample count")
+    }
+
+    /// Like [`MultiDimArray::from_image`] for 8-bit images, but with
+    /// [`ElementType::U8Clamped`] elements.
+    pub fn from_image_clamped<P, B>(image: &ImageBuffer<P, B>) -> Self
+    where
+        P: Pixel<Subpixel = u8>,
+        B: Deref<Target = [u8]>,
+    {
+        let (shape, samples) = image_samples(image);
+        let typed = TypedArray::new(ElementType::U8Clamped, Endianness::Big, samples.to_vec())
+            .expect("single-byte elements have no length constraint");
+        MultiDimArray::new(shape, Order::RowMajor, typed)
+            .expect("image shape matches its sample count")
+    }
+}
+
+impl<C: AsRef<[u8]>> MultiDimArray<C> {
+    /// Copy a row-major `[height, width, channels]` array into an `image`
+    /// buffer of pixel type `P`.
+    ///
+    /// Fails unless the array is row-major, has three dimensions, its channel
+    /// count matches `P`, and `P::Subpixel` backs the element type (`u8` also
+    /// accepts [`ElementType::U8Clamped`]).
+    pub fn to_image<P>(&self) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, ConvertError>
+    where
+        P: Pixel,
+        P::Subpixel: Scalar,
+    {
+        if self.order() != Order::RowMajor {
+            return Err(ConvertError::Shape("images need a row-major array"));
+        }
+        let [height, width, channels] = *self.shape() else {
+            return Err(ConvertError::Shape(
+                "images need a [height, width, channels] array",
+            ));
+        };
+        if channels != usize::from(P::CHANNEL_COUNT) {
+            return Err(ConvertError::Shape(
+                "channel count does not match pixel type",
+            ));
+        }
+        let (Ok(width), Ok(height)) = (u32::try_from(width), u32::try_from(height)) else {
+            return Err(ConvertError::Shape("image dimensions exceed u32"));
+        };
+        let samples = self.array().to_vec::<P::Subpixel>()?;
+        Ok(ImageBuffer::from_raw(width, height, samples).expect("shape matches sample count"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{GrayImage, Luma, Rgb, Rgb32FImage, RgbImage, Rgba};
+
+    #[test]
+    fn gray_and_rgb_round_trip() {
+        let gray = GrayImage::from_fn(3, 2, |x, y| Luma([(x + 10 * y) as u8]));
+        let a = MultiDimArray::from_image(&gray, Endianness::Big);
+        assert_eq!(a.shape(), &[2, 3, 1]);
+        assert_eq!(a.array().to_vec::<u8>().unwrap(), [0, 1, 2, 10, 11, 12]);
+        assert_eq!(a.to_image::<Luma<u8>>().unwrap(), gray);
+
+        let rgb = RgbImage::from_fn(2, 2, |x, y| Rgb([x as u8, y as u8, 7]));
+        let a = MultiDimArray::from_image(&rgb, Endianness::Big);
+        assert_eq!(a.shape(), &[2, 2, 3]);
+        assert_eq!(a.to_image::<Rgb<u8>>().unwrap(), rgb);
+    }
+
+    #[test]
+    fn wide_samples_survive_cbor() {
+        let rgba = ImageBuffer::from_fn(2, 1, |x, _| Rgba([1000 * x as u16, 2, 3, u16::MAX]));
+        let a = MultiDimArray::from_image(&rgba, Endianness::Little);
+        let mut buf = Vec::new();
+        minicbor::encode(&a, &mut buf).unwrap();
+        let decoded: OwnedMultiDimArray = minicbor::decode(&buf).unwrap();
+        assert_eq!(decoded.to_image::<Rgba<u16>>().unwrap(), rgba);
+
+        let hdr = Rgb32FImage::from_fn(1, 2, |_, y| Rgb([0.5, y as f32, -1.0]));
+        let a = MultiDimArray::from_image(&hdr, Endianness::Big);
+        assert_eq!(a.array().element_type(), ElementType::F32);
+        assert_eq!(a.to_image::<Rgb<f32>>().unwrap(), hdr);
+    }
+
+    #[test]
+    fn clamped_is_an_alternative_8_bit_encoding() {
+        let rgb = RgbImage::from_pixel(2, 1, Rgb([250, 5, 0]));
+        let a = MultiDimArray::from_image_clamped(&rgb);
+        assert_eq!(a.array().element_type(), ElementType::U8Clamped);
+        assert_eq!(a.to_image::<Rgb<u8>>().unwrap(), rgb);
+    }
+
+    #[test]
+    fn ignores_samples_past_the_image() {
+        let gray: GrayImage = ImageBuffer::from_raw(2, 2, alloc::vec![1, 2, 3, 4, 5]).unwrap();
+        let a = MultiDimArray::from_image(&gray, Endianness::Big);
+        assert_eq!(a.shape(), [2, 2, 1]);
+        assert_eq!(a.array().to_vec::<u8>().unwrap(), [1, 2, 3, 4]);
+        let clamped = MultiDimArray::from_image_clamped(&gray);
+        assert_eq!(clamped.array().as_bytes(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn validates_shape_channels_and_type() {
+        let rgb = RgbImage::new(2, 2);
+        let a = MultiDimArray::from_image(&rgb, Endianness::Big);
+        assert!(matches!(
+            a.to_image::<Rgba<u8>>(),
+            Err(ConvertError::Shape(_))
+        ));
+        assert!(matches!(
+            a.to_image::<Rgb<u16>>(),
+            Err(ConvertError::ElementType { .. })
+        ));
+
+        let (shape, _, typed) = a.into_parts();
+        let flat = MultiDimArray::new(alloc::vec![4, 3], Order::RowMajor, typed.clone()).unwrap();
+        assert!(matches!(
+            flat.to_image::<Rgb<u8>>(),
+            Err(ConvertError::Shape(_))
+        ));
+        let column = MultiDimArray::new(shape, Order::ColumnMajor, typed).unwrap();
+        assert!(matches!(
+            column.to_image::<Rgb<u8>>(),
+            Err(ConvertError::Shape(_))
+        ));
+    }
+}
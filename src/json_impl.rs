@@ -0,0 +1,413 @@
+use crate::element::{self, Element, ElementType, Endianness};
+use crate::serde_impl::{array_from_values, Bare};
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use serde::ser::{self, Serialize, SerializeSeq, SerializeStruct, Serializer};
+use serde_json::Value;
+
+/// Largest integer JavaScript numbers hold exactly, `2^53 - 1`.
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// How NaN and the infinities appear in JSON, which has no literal for them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonFinite {
+    /// Written as `null`, as `JSON.stringify` does; `null` reads back as NaN.
+    #[default]
+    Null,
+    /// Written as `"NaN"`, `"Infinity"` or `"-Infinity"`.
+    String,
+    /// Refused in either direction.
+    Error,
+}
+
+/// How `u64`/`i64` values beyond ±(2^53 - 1), which JavaScript numbers cannot
+/// hold exactly, appear in JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LargeInteger {
+    /// Written as plain JSON numbers, exact but lossy for JavaScript readers.
+    Number,
+    /// Written as decimal strings such as `"18446744073709551615"`; smaller
+    /// values stay numbers.
+    #[default]
+    String,
+    /// Refused in either direction.
+    Error,
+}
+
+/// Policies for [`TypedArray::to_json`] and [`OwnedTypedArray::from_json`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    pub non_finite: NonFinite,
+    pub large_integers: LargeInteger,
+}
+
+/// Error converting a typed array to or from JSON.
+#[derive(Debug)]
+pub enum JsonError {
+    /// The text is not valid JSON.
+    Json(serde_json::Error),
+    /// The `type`, `endianness` or `values` field is missing or invalid.
+    Field(&'static str),
+    /// Value `index` is non-finite and [`NonFinite::Error`] is in effect.
+    NonFinite { index: usize },
+    /// Value `index` is a large integer and [`LargeInteger::Error`] is in
+    /// effect.
+    LargeInteger { index: usize },
+    /// Value `index` is not a number the element type can hold.
+    Value { index: usize },
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Json(e) => write!(f, "{e}"),
+            JsonError::Field(name) => write!(f, "missing or invalid field {name:?}"),
+            JsonError::NonFinite { index } => write!(f, "value {index} is not finite"),
+            JsonError::LargeInteger { index } => {
+                write!(f, "value {index} is beyond 2^53 - 1 in magnitude")
+            }
+            JsonError::Value { index } => {
+                write!(f, "value {index} does not fit the element type")
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A typed array that serializes as `{"type","endianness","values"}` under
+/// the given [`JsonOptions`]; see [`TypedArray::json`].
+pub struct Json<'a, C> {
+    array: &'a TypedArray<C>,
+    options: JsonOptions,
+}
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// JSON text of the form `{"type":"f32","endianness":"le","values":[...]}`.
+    pub fn to_json(&self, options: JsonOptions) -> Result<String, JsonError> {
+        self.json(options).check()?;
+        serde_json::to_string(&self.json(options)).map_err(JsonError::Json)
+    }
+
+    /// A [`Serialize`] view for embedding the array in a larger document.
+    ///
+    /// Serialization fails where [`TypedArray::to_json`] would.
+    pub fn json(&self, options: JsonOptions) -> Json<'_, C> {
+        Json {
+            array: self,
+            options,
+        }
+    }
+}
+
+impl<C: AsRef<[u8]>> Json<'_, C> {
+    fn check(&self) -> Result<(), JsonError> {
+        for (index, element) in self.array.iter().enumerate() {
+            if let JsonValue::Refused(error) = json_value(element, self.options) {
+                return Err(error(index));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C: AsRef<[u8]>> Serialize for Json<'_, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.check().map_err(ser::Error::custom)?;
+        let mut s = serializer.serialize_struct("TypedArray", 3)?;
+        s.serialize_field("type", &self.array.element_type())?;
+        s.serialize_field("endianness", &self.array.endianness())?;
+        s.serialize_field("values", &Values(self))?;
+        s.end()
+    }
+}
+
+struct Values<'a, 'b, C>(&'b Json<'a, C>);
+
+impl<C: AsRef<[u8]>> Serialize for Values<'_, '_, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_seq(Some(self.0.array.len()))?;
+        for element in self.0.array {
+            s.serialize_element(&json_value(element, self.0.options))?;
+        }
+        s.end()
+    }
+}
+
+/// How one element is written.
+enum JsonValue {
+    Number(Element),
+    Null,
+    Text(String),
+    Refused(fn(usize) -> JsonError),
+}
+
+fn json_value(element: Element, options: JsonOptions) -> JsonValue {
+    let large = match element {
+        Element::U64(v) => v > MAX_SAFE_INTEGER,
+        Element::I64(v) => v.unsigned_abs() > MAX_SAFE_INTEGER,
+        _ => false,
+    };
+    if large {
+        return match options.large_integers {
+            LargeInteger::Number => JsonValue::Number(element),
+            LargeInteger::String => JsonValue::Text(match element {
+                Element::U64(v) => v.to_string(),
+                other => other.to_i64().to_string(),
+            }),
+            LargeInteger::Error => JsonValue::Refused(|index| JsonError::LargeInteger { index }),
+        };
+    }
+    let v = element.to_f64();
+    if v.is_finite() {
+        return JsonValue::Number(element);
+    }
+    match options.non_finite {
+        NonFinite::Null => JsonValue::Null,
+        NonFinite::String if v.is_nan() => JsonValue::Text("NaN".into()),
+        NonFinite::String if v > 0.0 => JsonValue::Text("Infinity".into()),
+        NonFinite::String => JsonValue::Text("-Infinity".into()),
+        NonFinite::Error => JsonValue::Refused(|index| JsonError::NonFinite { index }),
+    }
+}
+
+impl Serialize for JsonValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            JsonValue::Number(element) => Bare(*element).serialize(serializer),
+            JsonValue::Null => serializer.serialize_unit(),
+            JsonValue::Text(text) => serializer.serialize_str(text),
+            JsonValue::Refused(_) => Err(ser::Error::custom("value refused by JSON policy")),
+        }
+    }
+}
+
+impl OwnedTypedArray {
+    /// Parse JSON text written by [`TypedArray::to_json`] under the same
+    /// policies.
+    ///
+    /// Only float arrays accept non-finite values; for any other element type
+    /// `null` and the [`NonFinite::String`] spellings are
+    /// [`JsonError::NonFinite`] whatever the policy.
+    pub fn from_json(text: &str, options: JsonOptions) -> Result<Self, JsonError> {
+        let value: Value = serde_json::from_str(text).map_err(JsonError::Json)?;
+        Self::from_json_value(&value, options)
+    }
+
+    /// Like [`OwnedTypedArray::from_json`], for an already parsed document.
+    pub fn from_json_value(value: &Value, options: JsonOptions) -> Result<Self, JsonError> {
+        let field = |name| value.get(name).and_then(Value::as_str);
+        let element_type: ElementType = field("type")
+            .and_then(|t| t.parse().ok())
+            .ok_or(JsonError::Field("type"))?;
+        let endianness: Endianness = field("endianness")
+            .and_then(|e| e.parse().ok())
+            .ok_or(JsonError::Field("endianness"))?;
+        let values = value
+            .get("values")
+            .and_then(Value::as_array)
+            .ok_or(JsonError::Field("values"))?;
+        let numbers = values
+            .iter()
+            .enumerate()
+            .map(|(index, v)| number(v, index, element_type, options))
+            .collect::<Result<Vec<_>, _>>()?;
+        array_from_values(element_type, endianness, &numbers)
+            .map_err(|index| JsonError::Value { index })
+    }
+}
+
+/// The float a [`NonFinite::String`] value stands for.
+fn non_finite(text: &str) -> Option<f64> {
+    match text {
+        "NaN" => Some(f64::NAN),
+        "Infinity" => Some(f64::INFINITY),
+        "-Infinity" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+}
+
+/// Read one value of an `element_type` array; 64-bit integer arrays apply
+/// the [`LargeInteger`] policy, and only float arrays the [`NonFinite`] one.
+fn number(
+    value: &Value,
+    index: usize,
+    element_type: ElementType,
+    options: JsonOptions,
+) -> Result<element::Value, JsonError> {
+    let wide = matches!(element_type, ElementType::U64 | ElementType::I64);
+    let float = !matches!(
+        element_type,
+        ElementType::U8
+            | ElementType::U8Clamped
+            | ElementType::U16
+            | ElementType::U32
+            | ElementType::U64
+            | ElementType::I8
+            | ElementType::I16
+            | ElementType::I32
+            | ElementType::I64
+    );
+    let n = match value {
+        Value::Number(n) => match (n.as_u64(), n.as_i64()) {
+            (Some(u), _) => element::Value::Int(u.into()),
+            (None, Some(i)) => element::Value::Int(i.into()),
+            _ => element::Value::Float(n.as_f64().ok_or(JsonError::Value { index })?),
+        },
+        Value::Null if float && options.non_finite == NonFinite::Null => {
+            element::Value::Float(f64::NAN)
+        }
+        Value::Null => return Err(JsonError::NonFinite { index }),
+        Value::String(s) => match non_finite(s) {
+            Some(v) if float && options.non_finite == NonFinite::String => element::Value::Float(v),
+            Some(_) => return Err(JsonError::NonFinite { index }),
+            None if wide && options.large_integers == LargeInteger::String => {
+                match (s.parse::<u64>(), s.parse::<i64>()) {
+                    (Ok(u), _) => element::Value::Int(u.into()),
+                    (_, Ok(i)) => element::Value::Int(i.into()),
+                    _ => return Err(JsonError::Value { index }),
+                }
+            }
+            None => return Err(JsonError::Value { index }),
+        },
+        _ => return Err(JsonError::Value { index }),
+    };
+    let large = match n {
+        element::Value::Int(i) => i.unsigned_abs() > u128::from(MAX_SAFE_INTEGER),
+        element::Value::Float(_) => false,
+    };
+    if wide && large && options.large_integers == LargeInteger::Error {
+        return Err(JsonError::LargeInteger { index });
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(non_finite: NonFinite, large_integers: LargeInteger) -> JsonOptions {
+        JsonOptions {
+            non_finite,
+            large_integers,
+        }
+    }
+
+    #[test]
+    fn writes_and_reads_metadata_and_values() {
+        let a = TypedArray::from_slice(&[0.1f32, -2.5], Endianness::Little);
+        let json = a.to_json(JsonOptions::default()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"f32","endianness":"le","values":[0.1,-2.5]}"#
+        );
+        assert_eq!(
+            OwnedTypedArray::from_json(&json, JsonOptions::default()).unwrap(),
+            a
+        );
+
+        let doc = serde_json::json!({ "id": 7, "data": a.json(JsonOptions::default()) });
+        let back = OwnedTypedArray::from_json_value(&doc["data"], JsonOptions::default());
+        assert_eq!(back.unwrap(), a);
+        assert!(matches!(
+            OwnedTypedArray::from_json(r#"{"type":"f32","values":[]}"#, JsonOptions::default()),
+            Err(JsonError::Field("endianness"))
+        ));
+    }
+
+    #[test]
+    fn non_finite_policies() {
+        let a = TypedArray::from_slice(
+            &[f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1.0],
+            Endianness::Big,
+        );
+        let null = options(NonFinite::Null, LargeInteger::String);
+        let json = a.to_json(null).unwrap();
+        assert!(json.ends_with(r#""values":[null,null,null,1.0]}"#));
+        let back = OwnedTypedArray::from_json(&json, null)
+            .unwrap()
+            .to_vec::<f64>()
+            .unwrap();
+        assert!(back[0].is_nan() && back[1].is_nan() && back[3] == 1.0);
+
+        let string = options(NonFinite::String, LargeInteger::String);
+        let json = a.to_json(string).unwrap();
+        assert!(json.ends_with(r#""values":["NaN","Infinity","-Infinity",1.0]}"#));
+        let back = OwnedTypedArray::from_json(&json, string)
+            .unwrap()
+            .to_vec::<f64>()
+            .unwrap();
+        assert!(back[0].is_nan());
+        assert_eq!(back[1..], [f64::INFINITY, f64::NEG_INFINITY, 1.0]);
+
+        let error = options(NonFinite::Error, LargeInteger::String);
+        assert!(matches!(
+            a.to_json(error),
+            Err(JsonError::NonFinite { index: 0 })
+        ));
+        assert!(matches!(
+            OwnedTypedArray::from_json(&json, error),
+            Err(JsonError::NonFinite { index: 0 })
+        ));
+        assert!(serde_json::to_string(&a.json(error)).is_err());
+    }
+
+    #[test]
+    fn integer_arrays_refuse_non_finite_values() {
+        let null = options(NonFinite::Null, LargeInteger::String);
+        let string = options(NonFinite::String, LargeInteger::String);
+        for t in ["u8clamped", "i32"] {
+            let json = format!(r#"{{"type":"{t}","endianness":"be","values":[1,null]}}"#);
+            assert!(matches!(
+                OwnedTypedArray::from_json(&json, null),
+                Err(JsonError::NonFinite { index: 1 })
+            ));
+            let json = format!(r#"{{"type":"{t}","endianness":"be","values":["NaN"]}}"#);
+            assert!(matches!(
+                OwnedTypedArray::from_json(&json, string),
+                Err(JsonError::NonFinite { index: 0 })
+            ));
+        }
+        let json = r#"{"type":"u8clamped","endianness":"be","values":[300]}"#;
+        assert!(matches!(
+            OwnedTypedArray::from_json(json, null),
+            Err(JsonError::Value { index: 0 })
+        ));
+    }
+
+    #[test]
+    fn large_integer_policies() {
+        let a = TypedArray::from_slice(&[1i64, -(1 << 53), i64::MAX], Endianness::Little);
+        let string = options(NonFinite::Null, LargeInteger::String);
+        let json = a.to_json(string).unwrap();
+        assert!(json.ends_with(r#""values":[1,"-9007199254740992","9223372036854775807"]}"#));
+        assert_eq!(OwnedTypedArray::from_json(&json, string).unwrap(), a);
+
+        let number = options(NonFinite::Null, LargeInteger::Number);
+        let json = a.to_json(number).unwrap();
+        assert!(json.ends_with(r#""values":[1,-9007199254740992,9223372036854775807]}"#));
+        assert_eq!(OwnedTypedArray::from_json(&json, number).unwrap(), a);
+        assert!(OwnedTypedArray::from_json(&a.to_json(string).unwrap(), number).is_err());
+
+        let error = options(NonFinite::Null, LargeInteger::Error);
+        assert!(matches!(
+            a.to_json(error),
+            Err(JsonError::LargeInteger { index: 1 })
+        ));
+        assert!(matches!(
+            OwnedTypedArray::from_json(&json, error),
+            Err(JsonError::LargeInteger { index: 1 })
+        ));
+        let safe = TypedArray::from_slice(&[u64::from(u32::MAX)], Endianness::Big);
+        assert!(safe.to_json(error).is_ok());
+    }
+}
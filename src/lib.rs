@@ -5,18 +5,99 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+mod aligned;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+#[cfg(feature = "alloc")]
+mod arith;
+#[cfg(feature = "arrow")]
+mod arrow_impl;
+#[cfg(feature = "async")]
+mod async_io;
+mod convert;
+#[cfg(feature = "csv")]
+mod csv_impl;
+#[cfg(feature = "defmt")]
+mod defmt_impl;
+#[cfg(feature = "alloc")]
+mod edn;
 mod element;
+mod encode;
+#[cfg(feature = "image")]
+mod image_impl;
+#[cfg(feature = "json")]
+mod json_impl;
+#[cfg(feature = "alloc")]
+mod locate;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "alloc")]
+mod multi_dim;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_impl;
+#[cfg(feature = "ndarray")]
+mod ndarray_impl;
+#[cfg(feature = "alloc")]
+mod npy;
+#[cfg(feature = "num-traits")]
+mod num_traits_impl;
+#[cfg(feature = "proptest")]
+mod proptest_impl;
+mod read;
+mod reduce;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod tag;
+#[cfg(feature = "alloc")]
+mod transcode;
 mod typed_array;
+#[cfg(feature = "alloc")]
+mod walk;
 
-pub use element::{Element, ElementType, Endianness, Scalar};
+#[cfg(feature = "alloc")]
+pub use aligned::{AlignedBytes, AlignedTypedArray};
+#[cfg(feature = "alloc")]
+pub use arith::{ArithmeticError, ArithmeticOptions, Op, Operand, Overflow};
+#[cfg(feature = "async")]
+pub use async_io::{encode_async, AsyncElementReader};
+pub use convert::ConvertError;
+#[cfg(feature = "csv")]
+pub use csv_impl::{read_csv, write_csv, ColumnRef, CsvColumn, CsvError, CsvOptions};
+#[cfg(feature = "alloc")]
+pub use edn::EdnError;
+pub use element::{Element, ElementType, Endianness, Scalar, UnknownName};
+pub use encode::encode_iter;
 #[cfg(feature = "half")]
 pub use half;
+#[cfg(feature = "json")]
+pub use json_impl::{Json, JsonError, JsonOptions, LargeInteger, NonFinite};
+#[cfg(feature = "alloc")]
+pub use locate::{locate, Location};
+#[cfg(feature = "mmap")]
+pub use mmap::MappedFile;
+#[cfg(feature = "alloc")]
+pub use multi_dim::{InvalidShape, MultiDimArray, MultiDimArrayRef, Order, OwnedMultiDimArray};
+#[cfg(feature = "alloc")]
+pub use npy::NpyError;
+#[cfg(feature = "num-traits")]
+pub use num_traits_impl::{Cast, CastError};
+#[cfg(feature = "embedded-io")]
+pub use read::EmbeddedSource;
+#[cfg(feature = "std")]
+pub use read::IoSource;
+pub use read::{ElementReader, ReadError, Source};
+pub use reduce::{NanPolicy, Sum};
+pub use tag::{Bf16Encoding, BF16_MARKER_TAG};
+#[cfg(feature = "alloc")]
+pub use transcode::{transcode, Normalize, TranscodeError};
 #[cfg(feature = "alloc")]
 pub use typed_array::OwnedTypedArray;
-pub use typed_array::{InvalidLength, Iter, TypedArray, TypedArrayRef};
+pub use typed_array::{BufferTooSmall, InvalidLength, Iter, TypedArray, TypedArrayRef};
+#[cfg(feature = "alloc")]
+pub use walk::{Found, Path, PathSegment, TypedArrays};
 
-use crate::tag::element_type_from_tag;
+use crate::tag::{encode_tag, TagResolver};
 
 impl<C, Ctx> minicbor::Encode<Ctx> for TypedArray<C>
 where
@@ -27,10 +108,32 @@ where
         e: &mut minicbor::Encoder<W>,
         _ctx: &mut Ctx,
     ) -> Result<(), minicbor::encode::Error<W::Error>> {
-        let tag = self.element_type().tag(self.endianness());
-        e.tag(tag)?.bytes(self.as_bytes())?;
+        self.encode_with(e, Bf16Encoding::default())
+    }
+}
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// Encode like the `Encode` impl, tagging `bf16` arrays per `bf16`.
+    pub fn encode_with<W: minicbor::encode::Write>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+        bf16: Bf16Encoding,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        encode_tag(e, self.element_type(), self.endianness(), bf16)?;
+        e.bytes(self.as_bytes())?;
         Ok(())
     }
+
+    /// Decode like the `Decode` impls, recognizing `bf16` arrays per `bf16`.
+    pub fn decode_with<'b>(
+        d: &mut minicbor::Decoder<'b>,
+        bf16: Bf16Encoding,
+    ) -> Result<Self, minicbor::decode::Error>
+    where
+        C: From<&'b [u8]>,
+    {
+        decode_typed_array(d, bf16, C::from)
+    }
 }
 
 impl<'b, Ctx> minicbor::Decode<'b, Ctx> for TypedArray<&'b [u8]> {
@@ -38,14 +141,7 @@ impl<'b, Ctx> minicbor::Decode<'b, Ctx> for TypedArray<&'b [u8]> {
         d: &mut minicbor::Decoder<'b>,
         _ctx: &mut Ctx,
     ) -> Result<Self, minicbor::decode::Error> {
-        let tag = d.tag()?;
-        let (element_type, endianness) = element_type_from_tag(tag)?;
-        let bytes = d.bytes()?;
-        TypedArray::new(element_type, endianness, bytes).map_err(|_| {
-            minicbor::decode::Error::message(
-                "typed array byte length is not a multiple of element width",
-            )
-        })
+        decode_typed_array(d, Bf16Encoding::default(), |bytes| bytes)
     }
 }
 
@@ -55,13 +151,36 @@ impl<'b, Ctx> minicbor::Decode<'b, Ctx> for TypedArray<alloc::vec::Vec<u8>> {
         d: &mut minicbor::Decoder<'b>,
         _ctx: &mut Ctx,
     ) -> Result<Self, minicbor::decode::Error> {
-        let tag = d.tag()?;
-        let (element_type, endianness) = element_type_from_tag(tag)?;
-        let bytes = d.bytes()?.to_vec();
-        TypedArray::new(element_type, endianness, bytes).map_err(|_| {
-            minicbor::decode::Error::message(
-                "typed array byte length is not a multiple of element width",
-            )
-        })
+        decode_typed_array(d, Bf16Encoding::default(), <[u8]>::to_vec)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'b, Ctx> minicbor::Decode<'b, Ctx> for TypedArray<AlignedBytes> {
+    fn decode(
+        d: &mut minicbor::Decoder<'b>,
+        _ctx: &mut Ctx,
+    ) -> Result<Self, minicbor::decode::Error> {
+        decode_typed_array(d, Bf16Encoding::default(), AlignedBytes::from)
     }
 }
+
+/// Decode a tagged typed array, moving its payload into storage `C`.
+fn decode_typed_array<'b, C: AsRef<[u8]>>(
+    d: &mut minicbor::Decoder<'b>,
+    bf16: Bf16Encoding,
+    store: impl FnOnce(&'b [u8]) -> C,
+) -> Result<TypedArray<C>, minicbor::decode::Error> {
+    let mut tags = TagResolver::new(bf16);
+    let (element_type, endianness) = loop {
+        if let Some(resolved) = tags.feed(d.tag()?)? {
+            break resolved;
+        }
+    };
+    let bytes = store(d.bytes()?);
+    TypedArray::new(element_type, endianness, bytes).map_err(|_| {
+        minicbor::decode::Error::message(
+            "typed array byte length is not a multiple of element width",
+        )
+    })
+}
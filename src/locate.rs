@@ -0,0 +1,129 @@
+use crate::element::{ElementType, Endianness};
+use crate::walk::TypedArrays;
+use alloc::vec::Vec;
+use minicbor::decode::Error;
+use minicbor::Decoder;
+
+/// Where a typed array sits inside a CBOR buffer, and what it holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    /// Byte offset of the typed array's tag.
+    pub offset: usize,
+    /// Byte offset just past the end of its payload.
+    pub end: usize,
+    pub element_type: ElementType,
+    pub endianness: Endianness,
+    /// Number of elements.
+    pub len: usize,
+}
+
+/// Find every typed array in a CBOR item or CBOR sequence.
+///
+/// Runs [`TypedArrays`] over each item of the sequence and keeps only where
+/// each array sits; use [`TypedArrays`] directly for paths and shapes. Only
+/// definite-length payloads are reported, since only those can be borrowed in
+/// place; typed arrays with indefinite-length payloads are skipped, as are
+/// payloads that are not a whole number of elements and arrays inside map
+/// keys. Every reported range decodes as a [`crate::TypedArrayRef`].
+pub fn locate(bytes: &[u8]) -> Result<Vec<Location>, Error> {
+    let mut d = Decoder::new(bytes);
+    let mut out = Vec::new();
+    while d.position() < bytes.len() {
+        for found in TypedArrays::new(&mut d) {
+            let found = found?;
+            out.push(Location {
+                offset: found.offset,
+                end: found.end,
+                element_type: found.array.element_type(),
+                endianness: found.array.endianness(),
+                len: found.array.len(),
+            });
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TypedArray, TypedArrayRef};
+
+    #[test]
+    fn finds_nested_arrays() {
+        let a = TypedArray::from_slice(&[1u16, 2], Endianness::Little);
+        let b = TypedArray::from_slice(&[1.0f32], Endianness::Big);
+        let mut buf = alloc::vec::Vec::new();
+        let mut e = minicbor::Encoder::new(&mut buf);
+        e.map(2).unwrap();
+        e.str("a").unwrap().encode(&a).unwrap();
+        e.str("b").unwrap().begin_array().unwrap();
+        e.tag(minicbor::data::Tag::new(0))
+            .unwrap()
+            .str("x")
+            .unwrap();
+        e.encode(&b).unwrap().end().unwrap();
+        e.u8(7).unwrap();
+
+        let found = locate(&buf).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].element_type, ElementType::U16);
+        assert_eq!(found[0].len, 2);
+        assert_eq!(found[1].endianness, Endianness::Big);
+        let decoded: TypedArrayRef = minicbor::decode(&buf[found[1].offset..found[1].end]).unwrap();
+        assert_eq!(decoded.as_bytes(), b.as_bytes());
+    }
+
+    #[test]
+    fn skips_unrelated_tags_and_indefinite_payloads() {
+        let mut buf = alloc::vec::Vec::new();
+        let mut e = minicbor::Encoder::new(&mut buf);
+        e.tag(minicbor::data::Tag::new(1)).unwrap().u32(5).unwrap();
+        e.tag(ElementType::U8.tag(Endianness::Big))
+            .unwrap()
+            .begin_bytes()
+            .unwrap()
+            .bytes(&[1])
+            .unwrap()
+            .end()
+            .unwrap();
+        assert!(locate(&buf).unwrap().is_empty());
+    }
+
+    #[test]
+    fn skips_payloads_of_partial_elements() {
+        let buf = [0x82, 0xd8, 0x41, 0x43, 0x01, 0x02, 0x03, 0xd8, 0x40, 0x40];
+        let found = locate(&buf).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].offset, 7);
+        assert_eq!(found[0].element_type, ElementType::U8);
+        assert!(minicbor::decode::<TypedArrayRef>(&buf[found[0].offset..found[0].end]).is_ok());
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn reports_bf16_at_its_marker() {
+        let a = TypedArray::from_slice(&[half::bf16::ONE], Endianness::Big);
+        let mut buf = alloc::vec::Vec::new();
+        let mut e = minicbor::Encoder::new(&mut buf);
+        e.array(2).unwrap().encode(&a).unwrap();
+        // A marker around something other than a u16 array is scanned through.
+        e.tag(minicbor::data::Tag::new(crate::BF16_MARKER_TAG))
+            .unwrap()
+            .encode(TypedArray::from_slice(&[1u32], Endianness::Big))
+            .unwrap();
+
+        let found = locate(&buf).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].offset, 1);
+        assert_eq!(found[0].element_type, ElementType::Bf16);
+        assert_eq!(found[1].element_type, ElementType::U32);
+        let decoded: TypedArrayRef = minicbor::decode(&buf[found[0].offset..found[0].end]).unwrap();
+        assert_eq!(decoded.element_type(), ElementType::Bf16);
+    }
+
+    #[test]
+    fn rejects_excessive_nesting() {
+        let buf = alloc::vec![0x81; crate::walk::MAX_DEPTH + 2];
+        assert!(locate(&buf).is_err());
+    }
+}
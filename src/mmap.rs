@@ -0,0 +1,91 @@
+use crate::locate::{locate, Location};
+use crate::typed_array::TypedArrayRef;
+use alloc::vec::Vec;
+use minicbor::decode::Error;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// A CBOR file mapped into memory, from which typed arrays are borrowed
+/// without reading the file into a buffer.
+#[derive(Debug)]
+pub struct MappedFile {
+    map: memmap2::Mmap,
+}
+
+impl MappedFile {
+    /// Map the file at `path` read-only.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other
+    /// process, while the mapping or any array borrowed from it is alive.
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: upheld by the caller.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self { map })
+    }
+
+    /// The mapped file contents.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Find every typed array in the file; see [`crate::locate`].
+    pub fn locate(&self) -> Result<Vec<Location>, Error> {
+        locate(&self.map)
+    }
+
+    /// Decode the typed array whose tag starts at byte `offset`, borrowing
+    /// its payload from the mapping.
+    pub fn array_at(&self, offset: usize) -> Result<TypedArrayRef<'_>, Error> {
+        let bytes = self
+            .map
+            .get(offset..)
+            .ok_or_else(|| Error::message("offset is past the end of the file"))?;
+        minicbor::decode(bytes)
+    }
+
+    /// Decode every typed array in the file, borrowing from the mapping.
+    pub fn arrays(&self) -> Result<Vec<TypedArrayRef<'_>>, Error> {
+        self.locate()?
+            .iter()
+            .map(|loc| self.array_at(loc.offset))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Endianness, TypedArray};
+    use std::io::Write;
+
+    #[test]
+    fn borrows_arrays_from_mapped_file() {
+        let a = TypedArray::from_slice(&[1i32, -2, 3], Endianness::Little);
+        let b = TypedArray::from_slice(&[0.5f64], Endianness::Big);
+        let mut buf = Vec::new();
+        let mut e = minicbor::Encoder::new(&mut buf);
+        e.array(2).unwrap().encode(&a).unwrap().encode(&b).unwrap();
+
+        let path = std::env::temp_dir().join(std::format!(
+            "minicbor-typed-arrays-mmap-{}.cbor",
+            std::process::id()
+        ));
+        File::create(&path).unwrap().write_all(&buf).unwrap();
+        // SAFETY: the file is private to this test and not modified below.
+        let file = unsafe { MappedFile::open(&path) }.unwrap();
+        assert_eq!(file.as_bytes(), &buf[..]);
+
+        let arrays = file.arrays().unwrap();
+        assert_eq!(arrays.len(), 2);
+        assert_eq!(arrays[0].as_bytes(), a.as_bytes());
+        assert_eq!(arrays[1].as_bytes(), b.as_bytes());
+        assert!(file.array_at(buf.len() + 1).is_err());
+
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
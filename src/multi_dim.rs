@@ -0,0 +1,210 @@
+use crate::typed_array::TypedArray;
+use alloc::vec::Vec;
+use core::fmt;
+use minicbor::data::{IanaTag, Tag};
+use minicbor::decode;
+
+/// Storage order of a multi-dimensional array's elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Last index varies fastest; RFC8746 tag 40.
+    RowMajor,
+    /// First index varies fastest; RFC8746 tag 1040.
+    ColumnMajor,
+}
+
+impl Order {
+    /// The RFC8746 tag for multi-dimensional arrays in this order.
+    pub fn tag(self) -> IanaTag {
+        match self {
+            Order::RowMajor => IanaTag::MultiDimArrayR,
+            Order::ColumnMajor => IanaTag::MultiDimArrayC,
+        }
+    }
+
+    pub(crate) fn from_tag(tag: Tag) -> Option<Order> {
+        match IanaTag::try_from(tag) {
+            Ok(IanaTag::MultiDimArrayR) => Some(Order::RowMajor),
+            Ok(IanaTag::MultiDimArrayC) => Some(Order::ColumnMajor),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned by [`MultiDimArray::new`] when the shape does not account
+/// for exactly the array's elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidShape {
+    /// Number of elements in the typed array.
+    pub len: usize,
+}
+
+impl fmt::Display for InvalidShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "shape does not describe {} elements", self.len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidShape {}
+
+/// An RFC8746 multi-dimensional array (tag 40 or 1040): a shape plus a typed
+/// array holding the elements in [`Order`].
+///
+/// Only typed-array payloads are supported, not arrays of CBOR numbers.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MultiDimArray<C> {
+    shape: Vec<usize>,
+    order: Order,
+    array: TypedArray<C>,
+}
+
+impl<C: AsRef<[u8]>> fmt::Debug for MultiDimArray<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MultiDimArray")
+            .field("shape", &self.shape)
+            .field("order", &self.order)
+            .field("array", &self.array)
+            .finish()
+    }
+}
+
+/// A multi-dimensional array borrowing its byte payload.
+pub type MultiDimArrayRef<'b> = MultiDimArray<&'b [u8]>;
+
+/// A multi-dimensional array owning its byte payload.
+pub type OwnedMultiDimArray = MultiDimArray<Vec<u8>>;
+
+impl<C: AsRef<[u8]>> MultiDimArray<C> {
+    /// Pair a shape with a typed array.
+    ///
+    /// The product of `shape` must equal `array.len()`.
+    pub fn new(
+        shape: Vec<usize>,
+        order: Order,
+        array: TypedArray<C>,
+    ) -> Result<Self, InvalidShape> {
+        let len = array.len();
+        let elements = shape.iter().try_fold(1usize, |acc, &d| acc.checked_mul(d));
+        if elements != Some(len) {
+            return Err(InvalidShape { len });
+        }
+        Ok(Self {
+            shape,
+            order,
+            array,
+        })
+    }
+
+    /// Length of each dimension, outermost first.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    /// The elements, in [`MultiDimArray::order`].
+    pub fn array(&self) -> &TypedArray<C> {
+        &self.array
+    }
+
+    pub fn into_parts(self) -> (Vec<usize>, Order, TypedArray<C>) {
+        (self.shape, self.order, self.array)
+    }
+}
+
+impl<C, Ctx> minicbor::Encode<Ctx> for MultiDimArray<C>
+where
+    C: AsRef<[u8]>,
+{
+    fn encode<W: minicbor::encode::Write>(
+        &self,
+        e: &mut minicbor::Encoder<W>,
+        ctx: &mut Ctx,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        e.tag(self.order.tag())?.array(2)?;
+        e.array(self.shape.len() as u64)?;
+        for &d in &self.shape {
+            e.u64(d as u64)?;
+        }
+        minicbor::Encode::encode(&self.array, e, ctx)
+    }
+}
+
+impl<'b, Ctx, C> minicbor::Decode<'b, Ctx> for MultiDimArray<C>
+where
+    C: AsRef<[u8]>,
+    TypedArray<C>: minicbor::Decode<'b, Ctx>,
+{
+    fn decode(d: &mut minicbor::Decoder<'b>, ctx: &mut Ctx) -> Result<Self, decode::Error> {
+        let order = Order::from_tag(d.tag()?)
+            .ok_or_else(|| decode::Error::message("not a multi-dimensional array tag"))?;
+        if d.array()? != Some(2) {
+            return Err(decode::Error::message(
+                "multi-dimensional array must be a two-element array",
+            ));
+        }
+        let rank = d
+            .array()?
+            .ok_or_else(|| decode::Error::message("dimensions must be a definite array"))?;
+        let mut shape = Vec::new();
+        for _ in 0..rank {
+            let dim = usize::try_from(d.u64()?)
+                .map_err(|_| decode::Error::message("dimension does not fit in usize"))?;
+            shape.push(dim);
+        }
+        let array = minicbor::Decode::decode(d, ctx)?;
+        MultiDimArray::new(shape, order, array)
+            .map_err(|_| decode::Error::message("dimensions do not match element count"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Endianness;
+
+    #[test]
+    fn new_checks_element_count() {
+        let a = TypedArray::from_slice(&[1u8, 2, 3, 4, 5, 6], Endianness::Big);
+        assert!(MultiDimArray::new(alloc::vec![2, 3], Order::RowMajor, a.clone()).is_ok());
+        assert_eq!(
+            MultiDimArray::new(alloc::vec![4, 2], Order::RowMajor, a.clone()),
+            Err(InvalidShape { len: 6 })
+        );
+        assert!(MultiDimArray::new(alloc::vec![usize::MAX, 2], Order::RowMajor, a).is_err());
+    }
+
+    #[test]
+    fn round_trips_both_orders() {
+        for order in [Order::RowMajor, Order::ColumnMajor] {
+            let a = TypedArray::from_slice(&[1.0f32, 2.0, 3.0, 4.0], Endianness::Little);
+            let m = MultiDimArray::new(alloc::vec![2, 2], order, a).unwrap();
+            let mut buf = Vec::new();
+            minicbor::encode(&m, &mut buf).unwrap();
+            assert_eq!(
+                minicbor::Decoder::new(&buf).tag().unwrap(),
+                order.tag().tag()
+            );
+            let owned: OwnedMultiDimArray = minicbor::decode(&buf).unwrap();
+            assert_eq!(owned, m);
+            let borrowed: MultiDimArrayRef = minicbor::decode(&buf).unwrap();
+            assert_eq!(borrowed.shape(), &[2, 2]);
+            assert_eq!(borrowed.order(), order);
+        }
+    }
+
+    #[test]
+    fn decode_rejects_mismatched_dimensions() {
+        let a = TypedArray::from_slice(&[1u16, 2, 3], Endianness::Big);
+        let mut buf = Vec::new();
+        let mut e = minicbor::Encoder::new(&mut buf);
+        e.tag(IanaTag::MultiDimArrayR).unwrap().array(2).unwrap();
+        e.array(2).unwrap().u8(2).unwrap().u8(2).unwrap();
+        e.encode(&a).unwrap();
+        let r: Result<OwnedMultiDimArray, _> = minicbor::decode(&buf);
+        assert!(r.is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::convert::ConvertError;
+use crate::element::{Endianness, Scalar};
+use crate::multi_dim::{MultiDimArray, Order, OwnedMultiDimArray};
+use crate::typed_array::TypedArray;
+use alloc::vec::Vec;
+use nalgebra as na;
+
+impl OwnedMultiDimArray {
+    /// Build a column-major (tag 1040) `[rows, cols]` array from any
+    /// `nalgebra` matrix, matching nalgebra's own storage order.
+    pub fn from_matrix<T, R, C, S>(matrix: &na::Matrix<T, R, C, S>, endianness: Endianness) -> Self
+    where
+        T: Scalar + na::Scalar,
+        R: na::Dim,
+        C: na::Dim,
+        S: na::RawStorage<T, R, C>,
+    {
+        let values: Vec<T> = matrix.iter().copied().collect();
+        MultiDimArray::new(
+            alloc::vec![matrix.nrows(), matrix.ncols()],
+            Order::ColumnMajor,
+            TypedArray::from_slice(&values, endianness),
+        )
+        .expect("matrix shape matches its element count")
+    }
+}
+
+impl<C: AsRef<[u8]>> MultiDimArray<C> {
+    /// The `[rows, cols]` shape, failing for any other rank.
+    fn matrix_shape(&self) -> Result<(usize, usize), ConvertError> {
+        match *self.shape() {
+            [rows, cols] => Ok((rows, cols)),
+            _ => Err(ConvertError::Shape("matrix needs a two-dimensional array")),
+        }
+    }
+
+    /// Copy into a dynamically sized `nalgebra` matrix.
+    ///
+    /// Accepts both orders: column-major arrays (tag 1040) map directly,
+    /// row-major ones (tag 40) are transposed into nalgebra's layout. `T` must
+    /// back the element type.
+    pub fn to_dmatrix<T: Scalar + na::Scalar>(&self) -> Result<na::DMatrix<T>, ConvertError> {
+        let (rows, cols) = self.matrix_shape()?;
+        let values = self.array().to_vec::<T>()?;
+        Ok(match self.order() {
+            Order::ColumnMajor => na::DMatrix::from_vec(rows, cols, values),
+            Order::RowMajor => na::DMatrix::from_row_slice(rows, cols, &values),
+        })
+    }
+
+    /// Copy into a statically sized `nalgebra` matrix, checking that the
+    /// array is `R` by `K`. See [`MultiDimArray::to_dmatrix`].
+    pub fn to_smatrix<T, const R: usize, const K: usize>(
+        &self,
+    ) -> Result<na::SMatrix<T, R, K>, ConvertError>
+    where
+        T: Scalar + na::Scalar,
+    {
+        if self.matrix_shape()? != (R, K) {
+            return Err(ConvertError::Shape("matrix dimensions do not match"));
+        }
+        let values = self.array().to_vec::<T>()?;
+        Ok(match self.order() {
+            Order::ColumnMajor => na::SMatrix::from_column_slice(&values),
+            Order::RowMajor => na::SMatrix::from_row_slice(&values),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dmatrix_round_trips_column_major() {
+        let m = na::DMatrix::from_row_slice(2, 3, &[1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let a = MultiDimArray::from_matrix(&m, Endianness::Little);
+        assert_eq!(a.shape(), &[2, 3]);
+        assert_eq!(a.order(), Order::ColumnMajor);
+        assert_eq!(
+            a.array().to_vec::<f64>().unwrap(),
+            [1.0, 4.0, 2.0, 5.0, 3.0, 6.0]
+        );
+
+        let mut buf = Vec::new();
+        minicbor::encode(&a, &mut buf).unwrap();
+        let decoded: OwnedMultiDimArray = minicbor::decode(&buf).unwrap();
+        assert_eq!(decoded.to_dmatrix::<f64>().unwrap(), m);
+    }
+
+    #[test]
+    fn row_major_is_transposed() {
+        let typed = TypedArray::from_slice(&[1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0], Endianness::Big);
+        let a = MultiDimArray::new(alloc::vec![2, 3], Order::RowMajor, typed).unwrap();
+        let m: na::SMatrix<f32, 2, 3> = a.to_smatrix().unwrap();
+        assert_eq!(m, na::Matrix2x3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
+        assert_eq!(
+            a.to_dmatrix::<f32>().unwrap(),
+            na::DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        );
+    }
+
+    #[test]
+    fn checks_shape_and_element_type() {
+        let m = na::Matrix2::new(1i16, 2, 3, 4);
+        let a = MultiDimArray::from_matrix(&m, Endianness::Big);
+        assert!(matches!(
+            a.to_smatrix::<i16, 2, 2>(),
+            Ok(back) if back == m
+        ));
+        assert!(matches!(
+            a.to_smatrix::<i16, 1, 4>(),
+            Err(ConvertError::Shape(_))
+        ));
+        assert!(matches!(
+            a.to_dmatrix::<u16>(),
+            Err(ConvertError::ElementType { .. })
+        ));
+        let flat = MultiDimArray::new(alloc::vec![4], Order::RowMajor, a.array().clone()).unwrap();
+        assert!(matches!(
+            flat.to_dmatrix::<i16>(),
+            Err(ConvertError::Shape(_))
+        ));
+    }
+}
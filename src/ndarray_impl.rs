@@ -0,0 +1,129 @@
+use crate::convert::ConvertError;
+use crate::element::{Endianness, Scalar};
+use crate::multi_dim::{MultiDimArray, Order, OwnedMultiDimArray};
+use crate::typed_array::TypedArray;
+use alloc::vec::Vec;
+use ndarray::{ArrayBase, ArrayD, ArrayViewD, Data, Dimension, IxDyn, ShapeBuilder, StrideShape};
+
+fn shape(dims: &[usize], order: Order) -> StrideShape<IxDyn> {
+    match order {
+        Order::RowMajor => IxDyn(dims).into(),
+        Order::ColumnMajor => IxDyn(dims).f().into(),
+    }
+}
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// Copy the elements into a one-dimensional `ndarray` array.
+    ///
+    /// `T` must back the element type.
+    pub fn to_ndarray<T: Scalar>(&self) -> Result<ArrayD<T>, ConvertError> {
+        let values = self.to_vec()?;
+        Ok(ArrayD::from_shape_vec(IxDyn(&[values.len()]), values)
+            .expect("one-dimensional shape matches length"))
+    }
+
+    /// Zero-copy one-dimensional `ndarray` view, when the payload can be
+    /// viewed as `&[T]` (see [`TypedArray::as_native_slice`]).
+    pub fn ndarray_view<T: Scalar>(&self) -> Option<ArrayViewD<'_, T>> {
+        let values = self.as_native_slice()?;
+        Some(
+            ArrayViewD::from_shape(IxDyn(&[values.len()]), values)
+                .expect("one-dimensional shape matches length"),
+        )
+    }
+}
+
+impl<C: AsRef<[u8]>> MultiDimArray<C> {
+    /// Copy the elements into an `ndarray` array of the same shape. Column-major
+    /// arrays become Fortran-layout arrays with the same logical indexing.
+    ///
+    /// `T` must back the element type.
+    pub fn to_ndarray<T: Scalar>(&self) -> Result<ArrayD<T>, ConvertError> {
+        let values = self.array().to_vec()?;
+        ArrayD::from_shape_vec(shape(self.shape(), self.order()), values)
+            .map_err(|_| ConvertError::Shape("shape is too large for ndarray"))
+    }
+
+    /// Zero-copy `ndarray` view of the same shape, when the payload can be
+    /// viewed as `&[T]` (see [`TypedArray::as_native_slice`]).
+    pub fn ndarray_view<T: Scalar>(&self) -> Option<ArrayViewD<'_, T>> {
+        let values = self.array().as_native_slice()?;
+        ArrayViewD::from_shape(shape(self.shape(), self.order()), values).ok()
+    }
+}
+
+impl OwnedMultiDimArray {
+    /// Build a row-major multi-dimensional array from any `ndarray` array.
+    ///
+    /// Arrays not in standard (C-contiguous) layout, such as transposed or
+    /// sliced views, are copied out in logical order.
+    pub fn from_ndarray<S, D>(array: &ArrayBase<S, D>, endianness: Endianness) -> Self
+    where
+        S: Data,
+        S::Elem: Scalar,
+        D: Dimension,
+    {
+        let typed = match array.as_slice() {
+            Some(values) => TypedArray::from_slice(values, endianness),
+            None => {
+                let values: Vec<S::Elem> = array.iter().copied().collect();
+                TypedArray::from_slice(&values, endianness)
+            }
+        };
+        MultiDimArray::new(array.shape().to_vec(), Order::RowMajor, typed)
+            .expect("ndarray shape matches its element count")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ElementType;
+    use ndarray::{arr2, Array3};
+
+    #[test]
+    fn round_trips_through_multi_dim() {
+        let a = arr2(&[[1i32, 2, 3], [4, 5, 6]]).into_dyn();
+        let m = MultiDimArray::from_ndarray(&a, Endianness::Big);
+        assert_eq!(m.shape(), &[2, 3]);
+        assert_eq!(m.order(), Order::RowMajor);
+        assert_eq!(m.to_ndarray::<i32>().unwrap(), a);
+        assert!(m.to_ndarray::<u32>().is_err());
+    }
+
+    #[test]
+    fn non_standard_layout_is_made_contiguous() {
+        let a = arr2(&[[1u16, 2, 3], [4, 5, 6]]);
+        let t = a.t();
+        let m = MultiDimArray::from_ndarray(&t, Endianness::Little);
+        assert_eq!(m.shape(), &[3, 2]);
+        assert_eq!(m.array().to_vec::<u16>().unwrap(), [1, 4, 2, 5, 3, 6]);
+        assert_eq!(m.to_ndarray::<u16>().unwrap(), t.into_dyn());
+    }
+
+    #[test]
+    fn column_major_keeps_logical_indexing() {
+        // Aligned storage, so the view below always exists.
+        let typed = crate::AlignedTypedArray::from_slice_aligned(
+            &[1.0f64, 2.0, 3.0, 4.0, 5.0, 6.0],
+            Endianness::NATIVE,
+        );
+        let m = MultiDimArray::new(alloc::vec![2, 3], Order::ColumnMajor, typed).unwrap();
+        let a = m.to_ndarray::<f64>().unwrap();
+        assert_eq!(a[[0, 1]], 3.0);
+        assert_eq!(a[[1, 0]], 2.0);
+        let view = m.ndarray_view::<f64>().expect("aligned native payload");
+        assert_eq!(view, a);
+    }
+
+    #[test]
+    fn views_aligned_native_payloads() {
+        let a = Array3::<u8>::from_shape_fn((2, 2, 2), |(i, j, k)| (i * 4 + j * 2 + k) as u8);
+        let m = MultiDimArray::from_ndarray(&a, Endianness::Big);
+        assert_eq!(m.array().element_type(), ElementType::U8);
+        assert_eq!(m.ndarray_view::<u8>().unwrap(), a.into_dyn());
+        let flat = m.array().ndarray_view::<u8>().unwrap();
+        assert_eq!(flat.len(), 8);
+        assert_eq!(m.array().to_ndarray::<u8>().unwrap(), flat);
+    }
+}
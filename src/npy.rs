@@ -0,0 +1,444 @@
+use crate::element::{ElementType, Endianness};
+use crate::multi_dim::{MultiDimArray, Order, OwnedMultiDimArray};
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write as _};
+
+// The `.npy` format: a magic string, a version, a little-endian header length
+// (u16 for v1, u32 for v2/v3), then a Python dict literal padded with spaces
+// and a newline so the data starts on a 64-byte boundary.
+const MAGIC: &[u8] = b"\x93NUMPY";
+const ALIGN: usize = 64;
+
+/// Error reading a NumPy `.npy` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NpyError {
+    /// The input does not start with the `.npy` magic string.
+    Magic,
+    /// Unsupported format version.
+    Version { major: u8, minor: u8 },
+    /// The header is truncated or is not a dict literal we understand.
+    Header(&'static str),
+    /// The `descr` dtype has no typed-array equivalent.
+    Dtype(String),
+    /// The shape does not fit the requested array kind.
+    Shape(&'static str),
+    /// The data section does not hold exactly the elements the header declares.
+    Data { expected: usize, actual: usize },
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpyError::Magic => f.write_str("not a .npy file"),
+            NpyError::Version { major, minor } => {
+                write!(f, "unsupported .npy version {major}.{minor}")
+            }
+            NpyError::Header(msg) => write!(f, "invalid .npy header: {msg}"),
+            NpyError::Dtype(descr) => write!(f, "unsupported dtype {descr:?}"),
+            NpyError::Shape(msg) => write!(f, "shape mismatch: {msg}"),
+            NpyError::Data { expected, actual } => write!(
+                f,
+                "data section has {actual} bytes, header declares {expected}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NpyError {}
+
+/// The NumPy `descr` string for an element type, e.g. `<f4` or `|u1`.
+///
+/// NumPy has no clamped or `bfloat16` type: [`ElementType::U8Clamped`] is
+/// written as `|u1` and `Bf16` as its raw `u2` bits.
+fn descr(element_type: ElementType, endianness: Endianness) -> String {
+    let kind = match element_type {
+        ElementType::U8
+        | ElementType::U8Clamped
+        | ElementType::U16
+        | ElementType::U32
+        | ElementType::U64 => 'u',
+        #[cfg(feature = "half")]
+        ElementType::Bf16 => 'u',
+        ElementType::I8 | ElementType::I16 | ElementType::I32 | ElementType::I64 => 'i',
+        #[cfg(feature = "half")]
+        ElementType::F16 => 'f',
+        ElementType::F32 | ElementType::F64 => 'f',
+    };
+    let order = match (element_type.width(), endianness) {
+        (1, _) => '|',
+        (_, Endianness::Big) => '>',
+        (_, Endianness::Little) => '<',
+    };
+    let mut out = String::new();
+    let _ = write!(out, "{order}{kind}{}", element_type.width());
+    out
+}
+
+fn parse_descr(descr: &str) -> Result<(ElementType, Endianness), NpyError> {
+    let unsupported = || NpyError::Dtype(descr.into());
+    let mut chars = descr.chars();
+    let endianness = match chars.next() {
+        Some('<') => Some(Endianness::Little),
+        Some('>') => Some(Endianness::Big),
+        Some('=') => Some(Endianness::NATIVE),
+        Some('|') => None,
+        _ => return Err(unsupported()),
+    };
+    let element_type = match chars.as_str() {
+        "u1" => ElementType::U8,
+        "u2" => ElementType::U16,
+        "u4" => ElementType::U32,
+        "u8" => ElementType::U64,
+        "i1" => ElementType::I8,
+        "i2" => ElementType::I16,
+        "i4" => ElementType::I32,
+        "i8" => ElementType::I64,
+        #[cfg(feature = "half")]
+        "f2" => ElementType::F16,
+        "f4" => ElementType::F32,
+        "f8" => ElementType::F64,
+        _ => return Err(unsupported()),
+    };
+    match endianness {
+        Some(endianness) => Ok((element_type, endianness)),
+        // `|` ("not applicable") only makes sense for single bytes.
+        None if element_type.width() == 1 => Ok((element_type, Endianness::Big)),
+        None => Err(unsupported()),
+    }
+}
+
+/// The fields of a parsed header dict.
+struct Header {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+/// A minimal parser for the Python literals `np.save` writes.
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_ws(&mut self) {
+        self.input = self.input.trim_start();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_ws();
+        match self.input.strip_prefix(c) {
+            Some(rest) => {
+                self.input = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, c: char, msg: &'static str) -> Result<(), NpyError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(NpyError::Header(msg))
+        }
+    }
+
+    fn string(&mut self) -> Result<&'a str, NpyError> {
+        self.skip_ws();
+        let quote = match self.input.chars().next() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return Err(NpyError::Header("expected a string")),
+        };
+        let rest = &self.input[1..];
+        let end = rest
+            .find(quote)
+            .ok_or(NpyError::Header("unterminated string"))?;
+        self.input = &rest[end + 1..];
+        Ok(&rest[..end])
+    }
+
+    fn boolean(&mut self) -> Result<bool, NpyError> {
+        self.skip_ws();
+        for (word, value) in [("True", true), ("False", false)] {
+            if let Some(rest) = self.input.strip_prefix(word) {
+                self.input = rest;
+                return Ok(value);
+            }
+        }
+        Err(NpyError::Header("expected True or False"))
+    }
+
+    fn usize(&mut self) -> Result<usize, NpyError> {
+        self.skip_ws();
+        let end = self
+            .input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.input.len());
+        let value = self.input[..end]
+            .parse()
+            .map_err(|_| NpyError::Header("expected a dimension"))?;
+        self.input = &self.input[end..];
+        Ok(value)
+    }
+
+    fn shape(&mut self) -> Result<Vec<usize>, NpyError> {
+        self.expect('(', "expected a shape tuple")?;
+        let mut shape = Vec::new();
+        while !self.eat(')') {
+            shape.push(self.usize()?);
+            if !self.eat(',') {
+                self.expect(')', "unterminated shape tuple")?;
+                break;
+            }
+        }
+        Ok(shape)
+    }
+
+    fn header(&mut self) -> Result<Header, NpyError> {
+        let (mut descr, mut fortran_order, mut shape) = (None, None, None);
+        self.expect('{', "expected a dict")?;
+        while !self.eat('}') {
+            let key = self.string()?;
+            self.expect(':', "expected ':' after key")?;
+            match key {
+                "descr" => {
+                    self.skip_ws();
+                    if self.input.starts_with('[') {
+                        return Err(NpyError::Dtype("structured dtype".into()));
+                    }
+                    descr = Some(self.string()?.into());
+                }
+                "fortran_order" => fortran_order = Some(self.boolean()?),
+                "shape" => shape = Some(self.shape()?),
+                _ => return Err(NpyError::Header("unknown key")),
+            }
+            if !self.eat(',') {
+                self.expect('}', "unterminated dict")?;
+                break;
+            }
+        }
+        match (descr, fortran_order, shape) {
+            (Some(descr), Some(fortran_order), Some(shape)) => Ok(Header {
+                descr,
+                fortran_order,
+                shape,
+            }),
+            _ => Err(NpyError::Header("missing key")),
+        }
+    }
+}
+
+/// Split a `.npy` file into its parsed header and data section.
+fn split(bytes: &[u8]) -> Result<(Header, &[u8]), NpyError> {
+    let rest = bytes.strip_prefix(MAGIC).ok_or(NpyError::Magic)?;
+    let truncated = NpyError::Header("truncated");
+    let (&[major, minor], rest) = rest.split_first_chunk().ok_or(truncated.clone())?;
+    let (header_len, rest) = match major {
+        1 => {
+            let (len, rest) = rest.split_first_chunk().ok_or(truncated.clone())?;
+            (u16::from_le_bytes(*len) as usize, rest)
+        }
+        2 | 3 => {
+            let (len, rest) = rest.split_first_chunk().ok_or(truncated.clone())?;
+            (u32::from_le_bytes(*len) as usize, rest)
+        }
+        _ => return Err(NpyError::Version { major, minor }),
+    };
+    if rest.len() < header_len {
+        return Err(truncated);
+    }
+    let (header, data) = rest.split_at(header_len);
+    // v1/v2 headers are latin-1, but `np.save` only ever writes ASCII there.
+    let header = core::str::from_utf8(header).map_err(|_| NpyError::Header("not text"))?;
+    Ok((Parser { input: header }.header()?, data))
+}
+
+/// Write a complete `.npy` file for `array` laid out in `shape` and `order`.
+fn write<C: AsRef<[u8]>>(shape: &[usize], order: Order, array: &TypedArray<C>) -> Vec<u8> {
+    let mut dict = String::new();
+    let _ = write!(
+        dict,
+        "{{'descr': '{}', 'fortran_order': {}, 'shape': (",
+        descr(array.element_type(), array.endianness()),
+        if order == Order::ColumnMajor {
+            "True"
+        } else {
+            "False"
+        },
+    );
+    for (i, d) in shape.iter().enumerate() {
+        let _ = write!(dict, "{}{d}", if i == 0 { "" } else { ", " });
+    }
+    dict.push_str(if shape.len() == 1 { ",), }" } else { "), }" });
+
+    // Use v1 unless the padded header overflows its u16 length.
+    let padded = |prefix: usize| (prefix + dict.len() + 1).next_multiple_of(ALIGN) - prefix;
+    let (version, prefix) = match padded(MAGIC.len() + 4) {
+        len if len <= u16::MAX as usize => (1, MAGIC.len() + 4),
+        _ => (2, MAGIC.len() + 6),
+    };
+    let header_len = padded(prefix);
+
+    let mut out = Vec::with_capacity(prefix + header_len + array.as_bytes().len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&[version, 0]);
+    if version == 1 {
+        out.extend_from_slice(&(header_len as u16).to_le_bytes());
+    } else {
+        out.extend_from_slice(&(header_len as u32).to_le_bytes());
+    }
+    out.extend_from_slice(dict.as_bytes());
+    out.resize(prefix + header_len - 1, b' ');
+    out.push(b'\n');
+    out.extend_from_slice(array.as_bytes());
+    out
+}
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// Serialize as a one-dimensional NumPy `.npy` file, keeping the byte order.
+    pub fn to_npy(&self) -> Vec<u8> {
+        write(&[self.len()], Order::RowMajor, self)
+    }
+}
+
+impl<C: AsRef<[u8]>> MultiDimArray<C> {
+    /// Serialize as a NumPy `.npy` file with the same shape; column-major
+    /// arrays are written with `fortran_order: True`.
+    pub fn to_npy(&self) -> Vec<u8> {
+        write(self.shape(), self.order(), self.array())
+    }
+}
+
+impl OwnedMultiDimArray {
+    /// Read a NumPy `.npy` file (format version 1, 2 or 3).
+    ///
+    /// Only plain integer and float dtypes are supported; `fortran_order: True`
+    /// becomes [`Order::ColumnMajor`].
+    pub fn from_npy(bytes: &[u8]) -> Result<Self, NpyError> {
+        let (header, data) = split(bytes)?;
+        let (element_type, endianness) = parse_descr(&header.descr)?;
+        let expected = header
+            .shape
+            .iter()
+            .try_fold(element_type.width(), |acc, &d| acc.checked_mul(d))
+            .ok_or(NpyError::Shape("shape is too large"))?;
+        if data.len() != expected {
+            return Err(NpyError::Data {
+                expected,
+                actual: data.len(),
+            });
+        }
+        let array = TypedArray::new(element_type, endianness, data.to_vec())
+            .expect("data length is a multiple of the width");
+        let order = if header.fortran_order {
+            Order::ColumnMajor
+        } else {
+            Order::RowMajor
+        };
+        Ok(MultiDimArray::new(header.shape, order, array).expect("data length matches shape"))
+    }
+}
+
+impl OwnedTypedArray {
+    /// Read a one-dimensional NumPy `.npy` file. See
+    /// [`MultiDimArray::from_npy`].
+    pub fn from_npy(bytes: &[u8]) -> Result<Self, NpyError> {
+        let array = MultiDimArray::from_npy(bytes)?;
+        if array.shape().len() != 1 {
+            return Err(NpyError::Shape("expected a one-dimensional array"));
+        }
+        Ok(array.into_parts().2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npy(version: u8, dict: &str, data: &[u8]) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        out.push(version);
+        out.push(0);
+        if version == 1 {
+            out.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+        } else {
+            out.extend_from_slice(&(dict.len() as u32).to_le_bytes());
+        }
+        out.extend_from_slice(dict.as_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+
+    #[test]
+    fn writes_numpy_layout() {
+        let a = TypedArray::from_slice(&[1i16, -2, 3], Endianness::Big);
+        let bytes = a.to_npy();
+        let dict = "{'descr': '>i2', 'fortran_order': False, 'shape': (3,), }";
+        assert_eq!(&bytes[..6], MAGIC);
+        assert_eq!(&bytes[6..10], &[1, 0, 118, 0]);
+        assert_eq!(&bytes[10..10 + dict.len()], dict.as_bytes());
+        assert_eq!(bytes[127], b'\n');
+        assert_eq!(&bytes[128..], a.as_bytes());
+        assert_eq!(OwnedTypedArray::from_npy(&bytes).unwrap(), a);
+    }
+
+    #[test]
+    fn round_trips_shape_and_order() {
+        let typed = TypedArray::from_slice(&[1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0], Endianness::Little);
+        for order in [Order::RowMajor, Order::ColumnMajor] {
+            let a = MultiDimArray::new(alloc::vec![2, 3], order, typed.clone()).unwrap();
+            assert_eq!(MultiDimArray::from_npy(&a.to_npy()).unwrap(), a);
+        }
+        let one = TypedArray::from_slice(&[7u8], Endianness::Big);
+        let scalar = MultiDimArray::new(Vec::new(), Order::RowMajor, one).unwrap();
+        let back = MultiDimArray::from_npy(&scalar.to_npy()).unwrap();
+        assert_eq!(back.shape(), &[] as &[usize]);
+    }
+
+    #[test]
+    fn reads_v2_headers_in_any_key_order() {
+        let data = [0, 0, 0x80, 0x3f];
+        let bytes = npy(
+            2,
+            "{\"shape\": (1,1), \"fortran_order\": True, \"descr\": \"<f4\"}\n",
+            &data,
+        );
+        let a = MultiDimArray::from_npy(&bytes).unwrap();
+        assert_eq!(a.shape(), &[1, 1]);
+        assert_eq!(a.order(), Order::ColumnMajor);
+        assert_eq!(a.array().to_vec::<f32>().unwrap(), [1.0]);
+        assert_eq!(
+            OwnedTypedArray::from_npy(&bytes),
+            Err(NpyError::Shape("expected a one-dimensional array"))
+        );
+    }
+
+    #[test]
+    fn reports_unsupported_input() {
+        let dict = |descr: &str| {
+            alloc::format!("{{'descr': {descr}, 'fortran_order': False, 'shape': (2,), }}\n")
+        };
+        let err = |descr: &str, data: &[u8]| {
+            MultiDimArray::from_npy(&npy(1, &dict(descr), data)).unwrap_err()
+        };
+        assert_eq!(err("'<c8'", &[0; 16]), NpyError::Dtype("<c8".into()));
+        assert_eq!(err("'|b1'", &[0; 2]), NpyError::Dtype("|b1".into()));
+        assert_eq!(err("'|u2'", &[0; 4]), NpyError::Dtype("|u2".into()));
+        assert!(matches!(err("[('a', '<i4')]", &[]), NpyError::Dtype(_)));
+        assert_eq!(
+            err("'<u4'", &[0; 4]),
+            NpyError::Data {
+                expected: 8,
+                actual: 4
+            }
+        );
+        assert_eq!(MultiDimArray::from_npy(b"PK\x03\x04"), Err(NpyError::Magic));
+        assert_eq!(
+            MultiDimArray::from_npy(&npy(4, "", &[])),
+            Err(NpyError::Version { major: 4, minor: 0 })
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use crate::element::Element;
+use crate::typed_array::Iter;
+use core::fmt;
+use core::marker::PhantomData;
+use num_traits::{NumCast, ToPrimitive};
+
+/// Evaluate `$body` with `$v` bound to the element's value.
+macro_rules! with_value {
+    ($element:expr, $v:ident => $body:expr) => {
+        match $element {
+            Element::U8($v) | Element::U8Clamped($v) => $body,
+            Element::U16($v) => $body,
+            Element::U32($v) => $body,
+            Element::U64($v) => $body,
+            Element::I8($v) => $body,
+            Element::I16($v) => $body,
+            Element::I32($v) => $body,
+            Element::I64($v) => $body,
+            #[cfg(feature = "half")]
+            Element::F16($v) => $body,
+            #[cfg(feature = "half")]
+            Element::Bf16($v) => $body,
+            Element::F32($v) => $body,
+            Element::F64($v) => $body,
+        }
+    };
+}
+
+macro_rules! delegate_to_primitive {
+    ( $( $method:ident -> $ty:ty ),+ $(,)? ) => {
+        $(
+            fn $method(&self) -> Option<$ty> {
+                with_value!(*self, v => ToPrimitive::$method(&v))
+            }
+        )+
+    };
+}
+
+/// Checked conversions of the element's value, as its scalar type performs
+/// them: `None` when the value does not fit, or is not finite for an integer
+/// target. Method syntax picks the inherent, lossy [`Element::to_i64`] and
+/// [`Element::to_f64`] first; call those two through the trait.
+impl ToPrimitive for Element {
+    delegate_to_primitive!(
+        to_isize -> isize,
+        to_i8 -> i8,
+        to_i16 -> i16,
+        to_i32 -> i32,
+        to_i64 -> i64,
+        to_i128 -> i128,
+        to_usize -> usize,
+        to_u8 -> u8,
+        to_u16 -> u16,
+        to_u32 -> u32,
+        to_u64 -> u64,
+        to_u128 -> u128,
+        to_f32 -> f32,
+        to_f64 -> f64,
+    );
+}
+
+impl Element {
+    /// Convert the value to `T` with [`NumCast`], or `None` if it does not fit.
+    pub fn cast<T: NumCast>(self) -> Option<T> {
+        T::from(self)
+    }
+}
+
+/// An element that does not fit the target type of [`Iter::cast`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CastError {
+    /// Position of the element in the array.
+    pub index: usize,
+    pub element: Element,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "element {} ({:?}) does not fit the target type",
+            self.index, self.element
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CastError {}
+
+impl<'a> Iter<'a> {
+    /// Convert each element to `T` with [`NumCast`], yielding an error for
+    /// elements that do not fit.
+    ///
+    /// Collect into `Result<Vec<T>, CastError>` to stop at the first one.
+    pub fn cast<T: NumCast>(self) -> Cast<'a, T> {
+        Cast {
+            iter: self,
+            index: 0,
+            _target: PhantomData,
+        }
+    }
+}
+
+/// Iterator returned by [`Iter::cast`].
+pub struct Cast<'a, T> {
+    iter: Iter<'a>,
+    index: usize,
+    _target: PhantomData<fn() -> T>,
+}
+
+impl<T: NumCast> Iterator for Cast<'_, T> {
+    type Item = Result<T, CastError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.iter.next()?;
+        let index = self.index;
+        self.index += 1;
+        Some(element.cast().ok_or(CastError { index, element }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T: NumCast> ExactSizeIterator for Cast<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Endianness, TypedArray};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn elements_convert_checked() {
+        assert_eq!(Element::I16(-1).to_u8(), None);
+        assert_eq!(Element::I16(-1).to_i8(), Some(-1));
+        assert_eq!(ToPrimitive::to_i64(&Element::U64(u64::MAX)), None);
+        assert_eq!(Element::U64(u64::MAX).to_u128(), Some(u64::MAX as u128));
+        assert_eq!(Element::F32(2.5).to_i32(), Some(2));
+        assert_eq!(Element::F64(f64::NAN).to_i32(), None);
+        assert_eq!(Element::U8Clamped(200).cast::<f32>(), Some(200.0));
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn half_elements_convert() {
+        assert_eq!(
+            Element::F16(half::f16::from_f32(1.5)).cast::<f64>(),
+            Some(1.5)
+        );
+        assert_eq!(
+            Element::Bf16(half::bf16::from_f32(-4.0)).cast::<i8>(),
+            Some(-4)
+        );
+    }
+
+    #[test]
+    fn iterators_cast_into_any_numeric_type() {
+        let a = TypedArray::from_slice(&[1u32, 2, 300], Endianness::Little);
+        let wide: Vec<f64> = a.iter().cast().collect::<Result<_, _>>().unwrap();
+        assert_eq!(wide, [1.0, 2.0, 300.0]);
+
+        let err = a
+            .iter()
+            .cast::<u8>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            CastError {
+                index: 2,
+                element: Element::U32(300)
+            }
+        );
+        assert_eq!(a.iter().cast::<u8>().len(), 3);
+    }
+
+    #[test]
+    fn generic_code_over_element_types() {
+        fn sum<T: NumCast + core::ops::Add<Output = T>>(array: &TypedArray<Vec<u8>>) -> Option<T> {
+            array
+                .iter()
+                .cast()
+                .try_fold(T::from(0)?, |acc, v| Some(acc + v.ok()?))
+        }
+        let a = TypedArray::from_slice(&[1i8, -2, 3], Endianness::Big);
+        assert_eq!(sum::<i64>(&a), Some(2));
+        assert_eq!(sum::<u16>(&a), None);
+        let b = TypedArray::from_slice(&[0.5f32, 0.25], Endianness::Little);
+        assert_eq!(sum::<f64>(&b), Some(0.75));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::element::{Element, ElementType, Endianness};
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use proptest::arbitrary::{any, Arbitrary};
+use proptest::collection::{vec, SizeRange};
+use proptest::sample::{select, Select};
+use proptest::strategy::{BoxedStrategy, Strategy};
+
+/// Longest array [`any::<OwnedTypedArray>()`](any) generates.
+const MAX_LEN: usize = 64;
+
+impl Arbitrary for Endianness {
+    type Parameters = ();
+    type Strategy = Select<Endianness>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        select(&[Endianness::Big, Endianness::Little][..])
+    }
+}
+
+impl Arbitrary for ElementType {
+    type Parameters = ();
+    type Strategy = Select<ElementType>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        select(ElementType::ALL)
+    }
+}
+
+impl Arbitrary for Element {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Element>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<ElementType>()
+            .prop_flat_map(Element::strategy)
+            .boxed()
+    }
+}
+
+impl Arbitrary for OwnedTypedArray {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<OwnedTypedArray>;
+
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        any::<ElementType>()
+            .prop_flat_map(|element_type| OwnedTypedArray::strategy(element_type, 0..=MAX_LEN))
+            .boxed()
+    }
+}
+
+impl Element {
+    /// Elements of `element_type` with any bit pattern, NaNs included.
+    pub fn strategy(element_type: ElementType) -> BoxedStrategy<Element> {
+        vec(any::<u8>(), element_type.width())
+            .prop_map(move |bytes| element_type.decode_chunk(&bytes, Endianness::Big))
+            .boxed()
+    }
+}
+
+impl OwnedTypedArray {
+    /// Arrays of `element_type` in either endianness, with an element count in
+    /// `len` and any payload bits.
+    pub fn strategy(
+        element_type: ElementType,
+        len: impl Into<SizeRange>,
+    ) -> BoxedStrategy<OwnedTypedArray> {
+        let element = vec(any::<u8>(), element_type.width());
+        (any::<Endianness>(), vec(element, len))
+            .prop_map(move |(endianness, elements)| {
+                TypedArray::new(element_type, endianness, elements.concat())
+                    .expect("whole elements")
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use proptest::proptest;
+
+    proptest! {
+        #[test]
+        fn arrays_round_trip(a in any::<OwnedTypedArray>()) {
+            let mut buf = Vec::new();
+            minicbor::encode(&a, &mut buf).unwrap();
+            assert_eq!(minicbor::decode::<OwnedTypedArray>(&buf).unwrap(), a);
+            assert!(a.len() <= MAX_LEN);
+        }
+
+        #[test]
+        fn strategies_respect_type_and_length(
+            a in OwnedTypedArray::strategy(ElementType::I32, 3..5),
+            e in Element::strategy(ElementType::U16),
+        ) {
+            assert_eq!(a.element_type(), ElementType::I32);
+            assert!((3..5).contains(&a.len()));
+            assert_eq!(e.element_type(), ElementType::U16);
+        }
+
+        #[test]
+        fn elements_write_back_their_bytes(e in any::<Element>(), end in any::<Endianness>()) {
+            let mut bytes = [0u8; 8];
+            let width = e.element_type().width();
+            e.write_into(end, &mut bytes[..width]);
+            let back = e.element_type().decode_chunk(&bytes[..width], end);
+            // Compare bits so that NaN payloads count as equal.
+            let mut again = [0u8; 8];
+            back.write_into(end, &mut again[..width]);
+            assert_eq!(bytes, again);
+        }
+    }
+}
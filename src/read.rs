@@ -0,0 +1,550 @@
+use crate::element::{Element, ElementType, Endianness, Scalar, MAX_WIDTH};
+use crate::tag::{Bf16Encoding, TagResolver};
+use core::fmt;
+use minicbor::data::Tag;
+use minicbor::decode;
+
+/// Error returned by [`ElementReader`].
+#[derive(Debug)]
+pub enum ReadError<E> {
+    /// The underlying reader failed.
+    Io(E),
+    /// The input is not a well-formed typed array, or ends early.
+    Decode(decode::Error),
+}
+
+impl<E: fmt::Display> fmt::Display for ReadError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "read error: {e}"),
+            ReadError::Decode(e) => write!(f, "decode error: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: std::error::Error + 'static> std::error::Error for ReadError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadError::Io(e) => Some(e),
+            ReadError::Decode(e) => Some(e),
+        }
+    }
+}
+
+impl<E> From<decode::Error> for ReadError<E> {
+    fn from(e: decode::Error) -> Self {
+        ReadError::Decode(e)
+    }
+}
+
+/// A blocking byte source an [`ElementReader`] pulls from.
+///
+/// Implemented by [`IoSource`] (for `std::io::Read`, under `std`) and
+/// [`EmbeddedSource`] (for `embedded_io::Read`, under `embedded-io`).
+pub trait Source {
+    type Error;
+    /// Fill `buf` completely.
+    ///
+    /// If the input ends first, fail with [`ReadError::Decode`] holding an
+    /// end-of-input error, whatever the underlying reader reports.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadError<Self::Error>>;
+}
+
+/// Adapts a `std::io::Read` into a [`Source`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoSource<R>(pub R);
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> Source for IoSource<R> {
+    type Error = std::io::Error;
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadError<Self::Error>> {
+        self.0.read_exact(buf).map_err(|e| match e.kind() {
+            std::io::ErrorKind::UnexpectedEof => decode::Error::end_of_input().into(),
+            _ => ReadError::Io(e),
+        })
+    }
+}
+
+/// Adapts an `embedded_io::Read` into a [`Source`].
+#[cfg(feature = "embedded-io")]
+#[derive(Debug)]
+pub struct EmbeddedSource<R>(pub R);
+
+#[cfg(feature = "embedded-io")]
+impl<R: embedded_io::Read> Source for EmbeddedSource<R> {
+    type Error = R::Error;
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadError<Self::Error>> {
+        self.0.read_exact(buf).map_err(|e| match e {
+            embedded_io::ReadExactError::UnexpectedEof => {
+                ReadError::Decode(decode::Error::end_of_input())
+            }
+            embedded_io::ReadExactError::Other(e) => ReadError::Io(e),
+        })
+    }
+}
+
+/// A CBOR data item head: major type plus argument (`None` for indefinite).
+pub(crate) struct Head {
+    pub(crate) major: u8,
+    pub(crate) arg: Option<u64>,
+}
+
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TAG: u8 = 6;
+const MAJOR_SIMPLE: u8 = 7;
+
+impl Head {
+    /// Number of argument bytes following an initial byte with `info`.
+    pub(crate) fn arg_len(info: u8) -> Result<usize, decode::Error> {
+        match info {
+            0..=23 | 31 => Ok(0),
+            24 => Ok(1),
+            25 => Ok(2),
+            26 => Ok(4),
+            27 => Ok(8),
+            _ => Err(decode::Error::message("reserved additional information")),
+        }
+    }
+
+    /// Build a head from its initial byte and big-endian argument bytes.
+    pub(crate) fn parse(initial: u8, arg: &[u8]) -> Head {
+        let info = initial & 0x1f;
+        let arg = match info {
+            0..=23 => Some(u64::from(info)),
+            31 => None,
+            _ => Some(arg.iter().fold(0u64, |acc, &b| (acc << 8) | u64::from(b))),
+        };
+        Head {
+            major: initial >> 5,
+            arg,
+        }
+    }
+
+    /// The tag number of a tag head.
+    pub(crate) fn tag(&self) -> Result<Tag, decode::Error> {
+        match *self {
+            Head {
+                major: MAJOR_TAG,
+                arg: Some(t),
+            } => Ok(Tag::new(t)),
+            _ => Err(decode::Error::message("expected a typed-array tag")),
+        }
+    }
+
+    fn is_break(&self) -> bool {
+        self.major == MAJOR_SIMPLE && self.arg.is_none()
+    }
+}
+
+/// Framing state shared by the blocking and async element readers.
+#[derive(Debug)]
+pub(crate) struct Framing {
+    pub(crate) element_type: ElementType,
+    pub(crate) endianness: Endianness,
+    /// Bytes left in the current byte-string segment.
+    pub(crate) segment: u64,
+    /// Element count of a definite-length payload.
+    pub(crate) len: Option<usize>,
+    pub(crate) indefinite: bool,
+    pub(crate) done: bool,
+}
+
+/// Most bytes `read_to_end` reserves up front. Segment lengths come from
+/// the input, so a larger payload grows the buffer as it is actually read.
+#[cfg(feature = "alloc")]
+const MAX_PREALLOC: u64 = 64 * 1024;
+
+impl Framing {
+    /// How many bytes to reserve for the rest of the payload.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn capacity_hint(&self) -> usize {
+        self.segment.min(MAX_PREALLOC) as usize
+    }
+
+    /// Feed the next tag head to `tags`; see [`TagResolver::feed`].
+    pub(crate) fn resolve(
+        tags: &mut TagResolver,
+        head: Head,
+    ) -> Result<Option<(ElementType, Endianness)>, decode::Error> {
+        tags.feed(head.tag()?)
+    }
+
+    /// Validate the byte-string head of a typed array whose tags resolved to
+    /// `element_type` and `endianness`.
+    pub(crate) fn start(
+        (element_type, endianness): (ElementType, Endianness),
+        bytes: Head,
+    ) -> Result<Framing, decode::Error> {
+        if bytes.major != MAJOR_BYTES {
+            return Err(decode::Error::message("expected a byte string"));
+        }
+        let width = element_type.width() as u64;
+        let (segment, len) = match bytes.arg {
+            Some(n) if n % width != 0 => {
+                return Err(decode::Error::message(
+                    "typed array byte length is not a multiple of element width",
+                ));
+            }
+            Some(n) => (n, usize::try_from(n / width).ok()),
+            None => (0, None),
+        };
+        Ok(Framing {
+            element_type,
+            endianness,
+            segment,
+            len,
+            indefinite: bytes.arg.is_none(),
+            done: false,
+        })
+    }
+
+    /// Whether another segment head must be read before payload bytes.
+    pub(crate) fn needs_segment(&self) -> bool {
+        self.segment == 0 && self.indefinite && !self.done
+    }
+
+    /// Consume the head of the next indefinite-length segment. Returns
+    /// `false` on the terminating break.
+    pub(crate) fn next_segment(&mut self, head: Head) -> Result<bool, decode::Error> {
+        if head.is_break() {
+            self.done = true;
+            return Ok(false);
+        }
+        match head {
+            Head {
+                major: MAJOR_BYTES,
+                arg: Some(n),
+            } => {
+                self.segment = n;
+                Ok(true)
+            }
+            _ => Err(decode::Error::message(
+                "indefinite byte string chunks must be definite byte strings",
+            )),
+        }
+    }
+
+    /// Account for the end of payload with `filled` bytes of a pending
+    /// element. Returns `false` if the payload ended cleanly.
+    pub(crate) fn finish(&mut self, filled: usize) -> Result<bool, decode::Error> {
+        self.done = true;
+        if filled == 0 {
+            Ok(false)
+        } else {
+            Err(decode::Error::message(
+                "typed array payload ends mid-element",
+            ))
+        }
+    }
+
+    /// How many payload bytes to read next towards an element that already
+    /// has `filled` of `width` bytes.
+    pub(crate) fn take(&mut self, filled: usize) -> usize {
+        let want = (self.element_type.width() - filled) as u64;
+        let take = want.min(self.segment);
+        self.segment -= take;
+        take as usize
+    }
+
+    pub(crate) fn decode<T: Scalar>(&self, bytes: &[u8]) -> T {
+        match self.endianness {
+            Endianness::Big => T::read_be(bytes),
+            Endianness::Little => T::read_le(bytes),
+        }
+    }
+}
+
+/// Incremental decoder for one typed array read from a byte [`Source`].
+///
+/// Parses the typed-array tag and byte-string header up front, then yields
+/// elements (or fixed-size chunks of `T` via [`ElementReader::read_chunk`])
+/// as they are read. Memory use is bounded by the caller's chunk buffer; the
+/// whole payload is never held at once. Indefinite-length byte strings are
+/// supported, including elements split across segment boundaries.
+///
+/// After a read fails partway through the payload the reader stops for good:
+/// later reads report the end of the payload, and the iterator is fused.
+#[derive(Debug)]
+pub struct ElementReader<S> {
+    source: S,
+    framing: Framing,
+    failed: bool,
+}
+
+impl<S: Source> ElementReader<S> {
+    /// Read the typed-array tag and byte-string header from `source`.
+    pub fn new(mut source: S) -> Result<Self, ReadError<S::Error>> {
+        let mut tags = TagResolver::new(Bf16Encoding::default());
+        let resolved = loop {
+            if let Some(resolved) = Framing::resolve(&mut tags, read_head(&mut source)?)? {
+                break resolved;
+            }
+        };
+        let framing = Framing::start(resolved, read_head(&mut source)?)?;
+        Ok(Self {
+            source,
+            framing,
+            failed: false,
+        })
+    }
+
+    pub fn element_type(&self) -> ElementType {
+        self.framing.element_type
+    }
+
+    pub fn endianness(&self) -> Endianness {
+        self.framing.endianness
+    }
+
+    /// Total number of elements, if the payload has a definite length.
+    pub fn definite_len(&self) -> Option<usize> {
+        self.framing.len
+    }
+
+    /// Give back the underlying source.
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Read the raw bytes of the next element into `out[..width]`. Returns
+    /// `false` at the end of the payload, or once a read has failed.
+    fn next_raw(&mut self, out: &mut [u8; MAX_WIDTH]) -> Result<bool, ReadError<S::Error>> {
+        if self.failed {
+            return Ok(false);
+        }
+        // An error can leave the framing partway through an element, so
+        // nothing after it can be trusted.
+        let r = self.fill(out);
+        self.failed = r.is_err();
+        r
+    }
+
+    fn fill(&mut self, out: &mut [u8; MAX_WIDTH]) -> Result<bool, ReadError<S::Error>> {
+        let width = self.framing.element_type.width();
+        let mut filled = 0;
+        while filled < width {
+            if self.framing.needs_segment() {
+                let head = read_head(&mut self.source)?;
+                if !self.framing.next_segment(head)? {
+                    return Ok(self.framing.finish(filled)?);
+                }
+                continue;
+            }
+            if self.framing.segment == 0 {
+                return Ok(self.framing.finish(filled)?);
+            }
+            let take = self.framing.take(filled);
+            self.source.read_exact(&mut out[filled..filled + take])?;
+            filled += take;
+        }
+        Ok(true)
+    }
+
+    /// Decode the next element, or `None` at the end of the payload.
+    pub fn next_element(&mut self) -> Result<Option<Element>, ReadError<S::Error>> {
+        let mut buf = [0u8; MAX_WIDTH];
+        if !self.next_raw(&mut buf)? {
+            return Ok(None);
+        }
+        let width = self.framing.element_type.width();
+        Ok(Some(
+            self.framing
+                .element_type
+                .decode_chunk(&buf[..width], self.framing.endianness),
+        ))
+    }
+
+    /// Decode up to `out.len()` elements into `out`, returning how many were
+    /// written; `0` means the payload is exhausted (or `out` is empty).
+    ///
+    /// `T` must back the array's element type. If a read fails, the elements
+    /// already written to `out` stay there but their count is not returned.
+    pub fn read_chunk<T: Scalar>(&mut self, out: &mut [T]) -> Result<usize, ReadError<S::Error>> {
+        if !self.framing.element_type.is_backed_by::<T>() {
+            return Err(decode::Error::message("scalar type does not match element type").into());
+        }
+        let width = self.framing.element_type.width();
+        let mut buf = [0u8; MAX_WIDTH];
+        for (n, slot) in out.iter_mut().enumerate() {
+            if !self.next_raw(&mut buf)? {
+                return Ok(n);
+            }
+            *slot = self.framing.decode(&buf[..width]);
+        }
+        Ok(out.len())
+    }
+
+    /// Read the rest of the payload into an owned typed array.
+    #[cfg(feature = "alloc")]
+    pub fn read_to_end(mut self) -> Result<crate::OwnedTypedArray, ReadError<S::Error>> {
+        let width = self.framing.element_type.width();
+        let mut bytes = alloc::vec::Vec::with_capacity(self.framing.capacity_hint());
+        let mut buf = [0u8; MAX_WIDTH];
+        while self.next_raw(&mut buf)? {
+            bytes.extend_from_slice(&buf[..width]);
+        }
+        // Only whole elements are collected, so `new` cannot fail.
+        Ok(
+            crate::TypedArray::new(self.framing.element_type, self.framing.endianness, bytes)
+                .expect("read_to_end collects whole elements"),
+        )
+    }
+}
+
+impl<S: Source> Iterator for ElementReader<S> {
+    type Item = Result<Element, ReadError<S::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_element().transpose()
+    }
+}
+
+impl<S: Source> core::iter::FusedIterator for ElementReader<S> {}
+
+fn read_head<S: Source>(source: &mut S) -> Result<Head, ReadError<S::Error>> {
+    let mut initial = [0u8; 1];
+    source.read_exact(&mut initial)?;
+    let mut arg = [0u8; 8];
+    let n = Head::arg_len(initial[0] & 0x1f)?;
+    source.read_exact(&mut arg[..n])?;
+    Ok(Head::parse(initial[0], &arg[..n]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TypedArray;
+    use alloc::vec::Vec;
+
+    fn reader(buf: &[u8]) -> ElementReader<IoSource<&[u8]>> {
+        ElementReader::new(IoSource(buf)).unwrap()
+    }
+
+    #[test]
+    fn reads_definite_payload() {
+        let array = TypedArray::from_slice(&[1u32, 2, 3], Endianness::Little);
+        let mut buf = Vec::new();
+        minicbor::encode(&array, &mut buf).unwrap();
+        let r = reader(&buf);
+        assert_eq!(r.element_type(), ElementType::U32);
+        assert_eq!(r.definite_len(), Some(3));
+        let got: Vec<Element> = r.map(Result::unwrap).collect();
+        assert_eq!(got, array.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reads_chunks() {
+        let values: Vec<i16> = (0..10).collect();
+        let mut buf = Vec::new();
+        minicbor::encode(TypedArray::from_slice(&values, Endianness::Big), &mut buf).unwrap();
+        let mut r = reader(&buf);
+        let mut chunk = [0i16; 4];
+        let mut got = Vec::new();
+        loop {
+            let n = r.read_chunk(&mut chunk).unwrap();
+            if n == 0 {
+                break;
+            }
+            got.extend_from_slice(&chunk[..n]);
+        }
+        assert_eq!(got, values);
+    }
+
+    #[test]
+    fn reads_indefinite_payload_split_mid_element() {
+        let mut buf = Vec::new();
+        let mut e = minicbor::Encoder::new(&mut buf);
+        e.tag(ElementType::U16.tag(Endianness::Big))
+            .unwrap()
+            .begin_bytes()
+            .unwrap()
+            .bytes(&[0x12])
+            .unwrap()
+            .bytes(&[0x34, 0x00])
+            .unwrap()
+            .bytes(&[])
+            .unwrap()
+            .bytes(&[0x01])
+            .unwrap()
+            .end()
+            .unwrap();
+        let r = reader(&buf);
+        assert_eq!(r.definite_len(), None);
+        let owned = r.read_to_end().unwrap();
+        assert_eq!(
+            owned,
+            TypedArray::from_slice(&[0x1234u16, 0x0001], Endianness::Big)
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_element() {
+        let mut buf = Vec::new();
+        let mut e = minicbor::Encoder::new(&mut buf);
+        e.tag(ElementType::U16.tag(Endianness::Big))
+            .unwrap()
+            .begin_bytes()
+            .unwrap()
+            .bytes(&[0x12, 0x34, 0x56])
+            .unwrap()
+            .end()
+            .unwrap();
+        let mut r = reader(&buf);
+        assert!(r.next_element().unwrap().is_some());
+        assert!(matches!(r.next_element(), Err(ReadError::Decode(_))));
+
+        // The reader stops after the error instead of reading on out of step.
+        let mut r = reader(&buf);
+        assert!(matches!(r.next(), Some(Ok(Element::U16(0x1234)))));
+        assert!(matches!(r.next(), Some(Err(_))));
+        assert!(r.next().is_none());
+        assert_eq!(r.read_chunk(&mut [0u16; 2]).unwrap(), 0);
+    }
+
+    #[test]
+    fn rejects_bad_headers() {
+        let mut buf = Vec::new();
+        minicbor::Encoder::new(&mut buf)
+            .tag(ElementType::U32.tag(Endianness::Big))
+            .unwrap()
+            .bytes(&[0; 6])
+            .unwrap();
+        assert!(ElementReader::new(IoSource(&buf[..])).is_err());
+        assert!(ElementReader::new(IoSource(&[0x01u8][..])).is_err());
+        assert!(matches!(
+            ElementReader::new(IoSource(&[0xd8u8][..])),
+            Err(ReadError::Decode(e)) if e.is_end_of_input()
+        ));
+    }
+
+    #[test]
+    fn huge_declared_length_is_not_preallocated() {
+        let header = [0xd8, 0x40, 0x5b, 0x40, 0, 0, 0, 0, 0, 0, 0];
+        assert!(reader(&header).read_to_end().is_err());
+    }
+
+    #[test]
+    fn read_chunk_rejects_mismatched_scalar() {
+        let mut buf = Vec::new();
+        minicbor::encode(TypedArray::from_slice(&[1u16], Endianness::Big), &mut buf).unwrap();
+        let mut r = reader(&buf);
+        assert!(r.read_chunk(&mut [0i16; 1]).is_err());
+    }
+
+    #[cfg(feature = "embedded-io")]
+    #[test]
+    fn reads_from_embedded_io() {
+        let mut buf = Vec::new();
+        minicbor::encode(TypedArray::from_slice(&[1.5f64], Endianness::Big), &mut buf).unwrap();
+        let mut r = ElementReader::new(EmbeddedSource(&buf[..])).unwrap();
+        assert_eq!(r.next_element().unwrap(), Some(Element::F64(1.5)));
+        assert_eq!(r.next_element().unwrap(), None);
+        let truncated = &buf[..buf.len() - 1];
+        let mut r = ElementReader::new(EmbeddedSource(truncated)).unwrap();
+        assert!(matches!(r.next_element(), Err(ReadError::Decode(_))));
+    }
+}
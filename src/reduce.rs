@@ -0,0 +1,310 @@
+use crate::element::{Element, ElementType, Endianness, Scalar, Value};
+use crate::typed_array::TypedArray;
+
+/// How [`TypedArray`]'s reductions treat NaN elements.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NanPolicy {
+    /// A NaN makes the result NaN; `argmin` and `argmax` return the index of
+    /// the first NaN.
+    #[default]
+    Propagate,
+    /// NaNs are left out, as if the array did not hold them.
+    Skip,
+}
+
+/// The sum of a typed array's elements.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sum {
+    /// Exact sum of an integer array.
+    Int(i128),
+    /// Compensated sum of a float array.
+    Float(f64),
+}
+
+impl Sum {
+    /// Lossy conversion to `f64`.
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Sum::Int(v) => v as f64,
+            Sum::Float(v) => v,
+        }
+    }
+}
+
+impl<C: AsRef<[u8]>> TypedArray<C> {
+    /// The smallest element, or `None` if there is none.
+    ///
+    /// Reads each element once, as its native type.
+    pub fn min(&self, nan: NanPolicy) -> Option<Element> {
+        self.argmin(nan).map(|i| self.element(i))
+    }
+
+    /// The largest element, or `None` if there is none.
+    pub fn max(&self, nan: NanPolicy) -> Option<Element> {
+        self.argmax(nan).map(|i| self.element(i))
+    }
+
+    /// Index of the first smallest element.
+    pub fn argmin(&self, nan: NanPolicy) -> Option<usize> {
+        reduce(self, Extreme { max: false, nan })
+    }
+
+    /// Index of the first largest element.
+    pub fn argmax(&self, nan: NanPolicy) -> Option<usize> {
+        reduce(self, Extreme { max: true, nan })
+    }
+
+    /// Sum of the elements: exact in `i128` for integer arrays, compensated
+    /// (Neumaier) in `f64` for float arrays.
+    pub fn sum(&self, nan: NanPolicy) -> Sum {
+        reduce(self, Total { nan }).0
+    }
+
+    /// Arithmetic mean, from [`TypedArray::sum`]; `None` if there are no
+    /// elements.
+    pub fn mean(&self, nan: NanPolicy) -> Option<f64> {
+        let (sum, count) = reduce(self, Total { nan });
+        (count > 0).then(|| sum.to_f64() / count as f64)
+    }
+
+    /// Population variance (dividing by the number of elements, not one
+    /// less); `None` if there are no elements.
+    pub fn variance(&self, nan: NanPolicy) -> Option<f64> {
+        reduce(self, Variance { nan })
+    }
+
+    pub(crate) fn element(&self, index: usize) -> Element {
+        let width = self.element_type().width();
+        let chunk = &self.as_bytes()[index * width..][..width];
+        self.element_type().decode_chunk(chunk, self.endianness())
+    }
+}
+
+/// A scalar the reductions can work on directly.
+trait Num: Scalar + PartialOrd {
+    const FLOAT: bool;
+    fn is_nan(self) -> bool;
+    fn value(self) -> Value;
+}
+
+macro_rules! impl_num {
+    ($float:literal, $nan:expr, $value:expr => $($ty:ty),+) => {
+        $(
+            impl Num for $ty {
+                const FLOAT: bool = $float;
+                fn is_nan(self) -> bool {
+                    $nan(self)
+                }
+                fn value(self) -> Value {
+                    $value(self)
+                }
+            }
+        )+
+    };
+}
+
+impl_num!(false, |_| false, |v| Value::Int(i128::from(v)) => u8, u16, u32, u64, i8, i16, i32, i64);
+impl_num!(true, f32::is_nan, |v| Value::Float(f64::from(v)) => f32);
+impl_num!(true, f64::is_nan, Value::Float => f64);
+#[cfg(feature = "half")]
+impl_num!(true, half::f16::is_nan, |v: half::f16| Value::Float(v.to_f64()) => half::f16);
+#[cfg(feature = "half")]
+impl_num!(true, half::bf16::is_nan, |v: half::bf16| Value::Float(v.to_f64()) => half::bf16);
+
+/// A reduction over the values of any [`Num`] type.
+trait Reduce {
+    type Output;
+    fn run<T: Num>(self, values: impl Iterator<Item = T>) -> Self::Output;
+}
+
+/// Run `r` over the array's values, choosing the element type once.
+fn reduce<C: AsRef<[u8]>, R: Reduce>(array: &TypedArray<C>, r: R) -> R::Output {
+    fn go<T: Num, R: Reduce>(bytes: &[u8], endianness: Endianness, r: R) -> R::Output {
+        let chunks = bytes.chunks_exact(core::mem::size_of::<T>());
+        match endianness {
+            Endianness::Big => r.run(chunks.map(T::read_be)),
+            Endianness::Little => r.run(chunks.map(T::read_le)),
+        }
+    }
+    let (bytes, endianness) = (array.as_bytes(), array.endianness());
+    match array.element_type() {
+        ElementType::U8 | ElementType::U8Clamped => go::<u8, R>(bytes, endianness, r),
+        ElementType::U16 => go::<u16, R>(bytes, endianness, r),
+        ElementType::U32 => go::<u32, R>(bytes, endianness, r),
+        ElementType::U64 => go::<u64, R>(bytes, endianness, r),
+        ElementType::I8 => go::<i8, R>(bytes, endianness, r),
+        ElementType::I16 => go::<i16, R>(bytes, endianness, r),
+        ElementType::I32 => go::<i32, R>(bytes, endianness, r),
+        ElementType::I64 => go::<i64, R>(bytes, endianness, r),
+        #[cfg(feature = "half")]
+        ElementType::F16 => go::<half::f16, R>(bytes, endianness, r),
+        #[cfg(feature = "half")]
+        ElementType::Bf16 => go::<half::bf16, R>(bytes, endianness, r),
+        ElementType::F32 => go::<f32, R>(bytes, endianness, r),
+        ElementType::F64 => go::<f64, R>(bytes, endianness, r),
+    }
+}
+
+struct Extreme {
+    max: bool,
+    nan: NanPolicy,
+}
+
+impl Reduce for Extreme {
+    type Output = Option<usize>;
+
+    fn run<T: Num>(self, values: impl Iterator<Item = T>) -> Option<usize> {
+        let mut best: Option<(usize, T)> = None;
+        for (i, v) in values.enumerate() {
+            if v.is_nan() {
+                match self.nan {
+                    NanPolicy::Propagate => return Some(i),
+                    NanPolicy::Skip => continue,
+                }
+            }
+            let better = match best {
+                None => true,
+                Some((_, b)) if self.max => v > b,
+                Some((_, b)) => v < b,
+            };
+            if better {
+                best = Some((i, v));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+}
+
+/// The sum and the number of elements summed.
+struct Total {
+    nan: NanPolicy,
+}
+
+impl Reduce for Total {
+    type Output = (Sum, usize);
+
+    fn run<T: Num>(self, values: impl Iterator<Item = T>) -> (Sum, usize) {
+        let mut count = 0;
+        let mut int = 0i128;
+        let (mut sum, mut compensation) = (0.0f64, 0.0f64);
+        for v in values {
+            if v.is_nan() && self.nan == NanPolicy::Skip {
+                continue;
+            }
+            count += 1;
+            match v.value() {
+                Value::Int(v) => int += v,
+                Value::Float(v) => {
+                    let t = sum + v;
+                    // Once the sum is infinite or NaN the error term is
+                    // meaningless, and would turn `inf` into NaN.
+                    if t.is_finite() {
+                        compensation += if sum.abs() >= v.abs() {
+                            (sum - t) + v
+                        } else {
+                            (v - t) + sum
+                        };
+                    }
+                    sum = t;
+                }
+            }
+        }
+        if !T::FLOAT {
+            return (Sum::Int(int), count);
+        }
+        let sum = if sum.is_finite() {
+            sum + compensation
+        } else {
+            sum
+        };
+        (Sum::Float(sum), count)
+    }
+}
+
+/// Welford's running variance.
+struct Variance {
+    nan: NanPolicy,
+}
+
+impl Reduce for Variance {
+    type Output = Option<f64>;
+
+    fn run<T: Num>(self, values: impl Iterator<Item = T>) -> Option<f64> {
+        let (mut count, mut mean, mut m2) = (0.0f64, 0.0f64, 0.0f64);
+        for v in values {
+            if v.is_nan() && self.nan == NanPolicy::Skip {
+                continue;
+            }
+            let x = v.value().to_f64();
+            count += 1.0;
+            let delta = x - mean;
+            mean += delta / count;
+            m2 += delta * (x - mean);
+        }
+        (count > 0.0).then(|| m2 / count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_reductions_are_exact() {
+        let a = TypedArray::from_slice(&[u64::MAX, 3, u64::MAX, 0], Endianness::Big);
+        assert_eq!(a.min(NanPolicy::Propagate), Some(Element::U64(0)));
+        assert_eq!(a.max(NanPolicy::Propagate), Some(Element::U64(u64::MAX)));
+        assert_eq!(a.argmax(NanPolicy::Propagate), Some(0));
+        assert_eq!(
+            a.sum(NanPolicy::Propagate),
+            Sum::Int(2 * i128::from(u64::MAX) + 3)
+        );
+
+        let c = TypedArray::new(ElementType::U8Clamped, Endianness::Big, &[8u8, 2, 2][..]).unwrap();
+        assert_eq!(c.min(NanPolicy::Skip), Some(Element::U8Clamped(2)));
+        assert_eq!(c.argmin(NanPolicy::Skip), Some(1));
+        assert_eq!(c.mean(NanPolicy::Skip), Some(4.0));
+        assert_eq!(c.variance(NanPolicy::Skip), Some(8.0));
+    }
+
+    #[test]
+    fn float_sums_are_compensated() {
+        let a = TypedArray::from_slice(&[1.0f64, 1e100, 1.0, -1e100], Endianness::Little);
+        assert_eq!(a.sum(NanPolicy::Propagate), Sum::Float(2.0));
+        assert_eq!(a.mean(NanPolicy::Propagate), Some(0.5));
+
+        let inf = TypedArray::from_slice(&[1.0f32, f32::INFINITY, 2.0], Endianness::Big);
+        assert_eq!(inf.sum(NanPolicy::Propagate), Sum::Float(f64::INFINITY));
+        assert_eq!(
+            inf.max(NanPolicy::Propagate),
+            Some(Element::F32(f32::INFINITY))
+        );
+    }
+
+    #[test]
+    fn nan_policy_decides() {
+        let a = TypedArray::from_slice(&[2.0f32, f32::NAN, -1.0, f32::NAN], Endianness::Little);
+        assert_eq!(a.argmin(NanPolicy::Propagate), Some(1));
+        assert!(a.max(NanPolicy::Propagate).unwrap().to_f64().is_nan());
+        assert!(a.sum(NanPolicy::Propagate).to_f64().is_nan());
+        assert!(a.variance(NanPolicy::Propagate).unwrap().is_nan());
+
+        assert_eq!(a.argmin(NanPolicy::Skip), Some(2));
+        assert_eq!(a.max(NanPolicy::Skip), Some(Element::F32(2.0)));
+        assert_eq!(a.mean(NanPolicy::Skip), Some(0.5));
+        assert_eq!(a.variance(NanPolicy::Skip), Some(2.25));
+    }
+
+    #[test]
+    fn empty_arrays_have_no_extremes_or_mean() {
+        let nans = TypedArray::from_slice(&[f64::NAN], Endianness::Big);
+        assert_eq!(nans.min(NanPolicy::Skip), None);
+        assert_eq!(nans.mean(NanPolicy::Skip), None);
+        assert_eq!(nans.sum(NanPolicy::Skip), Sum::Float(0.0));
+
+        let empty = TypedArray::from_slice::<i16>(&[], Endianness::Big);
+        assert_eq!(empty.argmax(NanPolicy::Propagate), None);
+        assert_eq!(empty.variance(NanPolicy::Propagate), None);
+        assert_eq!(empty.sum(NanPolicy::Propagate), Sum::Int(0));
+    }
+}
@@ -0,0 +1,397 @@
+use crate::element::{Element, ElementType, Endianness, Value};
+use crate::tag::element_type_from_tag;
+#[cfg(feature = "half")]
+use crate::tag::BF16_MARKER_TAG;
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use alloc::vec::Vec;
+use core::fmt;
+use minicbor::data::Tag;
+use serde::de::{self, Deserialize, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess};
+use serde::ser::{Serialize, SerializeSeq, SerializeStruct, SerializeTupleVariant, Serializer};
+
+// Binary formats see a typed array as the tuple variant `Tagged(tag, bytes)`
+// of this enum. The names follow ciborium's convention, so CBOR serializers
+// that honour it emit (and read) a real RFC8746 tag.
+const TAG_ENUM: &str = "@@TAG@@";
+const UNTAGGED: &str = "@@UNTAGGED@@";
+const TAGGED: &str = "@@TAGGED@@";
+const TAG_VARIANTS: &[&str] = &[UNTAGGED, TAGGED];
+
+const FIELDS: &[&str] = &["type", "endianness", "values"];
+
+/// Serializes as RFC8746 tag plus raw payload bytes in binary formats, and as
+/// `{ "type": "f32", "endianness": "le", "values": [...] }` in human-readable
+/// ones.
+impl<C: AsRef<[u8]>> Serialize for TypedArray<C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let mut s = serializer.serialize_struct("TypedArray", FIELDS.len())?;
+            s.serialize_field("type", &self.element_type())?;
+            s.serialize_field("endianness", &self.endianness())?;
+            s.serialize_field("values", &Values(self))?;
+            s.end()
+        } else {
+            let tag = self.element_type().tag(self.endianness()).tag().as_u64();
+            let mut s = serializer.serialize_tuple_variant(TAG_ENUM, 1, TAGGED, 2)?;
+            #[cfg(feature = "half")]
+            if self.element_type() == ElementType::Bf16 {
+                // `marker(tag(u16), bytes)`, as Bf16Encoding::default() writes.
+                s.serialize_field(&BF16_MARKER_TAG)?;
+                s.serialize_field(&Tagged(tag, self.as_bytes()))?;
+                return s.end();
+            }
+            s.serialize_field(&tag)?;
+            s.serialize_field(&Bytes(self.as_bytes()))?;
+            s.end()
+        }
+    }
+}
+
+/// A tag and byte string in binary formats.
+#[cfg(feature = "half")]
+struct Tagged<'a>(u64, &'a [u8]);
+
+#[cfg(feature = "half")]
+impl Serialize for Tagged<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_tuple_variant(TAG_ENUM, 1, TAGGED, 2)?;
+        s.serialize_field(&self.0)?;
+        s.serialize_field(&Bytes(self.1))?;
+        s.end()
+    }
+}
+
+/// Reads what [`Serialize`] writes. Human-readable values convert as
+/// [`Element::convert`] does, except that `u8clamped` values must already be
+/// integers from 0 to 255: out-of-range input is an error, not clamped.
+impl<'de> Deserialize<'de> for OwnedTypedArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_struct("TypedArray", FIELDS, HumanVisitor)
+        } else {
+            deserializer.deserialize_enum(TAG_ENUM, TAG_VARIANTS, TagVisitor)
+        }
+    }
+}
+
+/// The element values of a typed array, serialized as a plain number list.
+struct Values<'a, C>(&'a TypedArray<C>);
+
+impl<C: AsRef<[u8]>> Serialize for Values<'_, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_seq(Some(self.0.len()))?;
+        for element in self.0 {
+            s.serialize_element(&Bare(element))?;
+        }
+        s.end()
+    }
+}
+
+/// An element serialized as its bare number, without the variant name.
+pub(crate) struct Bare(pub(crate) Element);
+
+impl Serialize for Bare {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Element::U8(v) | Element::U8Clamped(v) => serializer.serialize_u8(v),
+            Element::U16(v) => serializer.serialize_u16(v),
+            Element::U32(v) => serializer.serialize_u32(v),
+            Element::U64(v) => serializer.serialize_u64(v),
+            Element::I8(v) => serializer.serialize_i8(v),
+            Element::I16(v) => serializer.serialize_i16(v),
+            Element::I32(v) => serializer.serialize_i32(v),
+            Element::I64(v) => serializer.serialize_i64(v),
+            #[cfg(feature = "half")]
+            Element::F16(v) => serializer.serialize_f32(v.to_f32()),
+            #[cfg(feature = "half")]
+            Element::Bf16(v) => serializer.serialize_f32(v.to_f32()),
+            Element::F32(v) => serializer.serialize_f32(v),
+            Element::F64(v) => serializer.serialize_f64(v),
+        }
+    }
+}
+
+struct Bytes<'a>(&'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+/// Owned payload bytes, accepted as a byte string or a sequence of `u8`.
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = ByteBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a byte string")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+                Ok(ByteBuf(v))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+                let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(b) = seq.next_element()? {
+                    out.push(b);
+                }
+                Ok(ByteBuf(out))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(Visitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a number")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+                Ok(Value::Int(v.into()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+                Ok(Value::Int(v.into()))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+                Ok(Value::Float(v))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Lay out values as a typed array of `element_type`, converted as
+/// [`Element::convert`] does but without clamping `u8clamped` values, failing
+/// with the index of the first value that does not fit.
+pub(crate) fn array_from_values(
+    element_type: ElementType,
+    endianness: Endianness,
+    values: &[Value],
+) -> Result<OwnedTypedArray, usize> {
+    let width = element_type.width();
+    let mut bytes = alloc::vec![0u8; values.len() * width];
+    for (i, (n, chunk)) in values.iter().zip(bytes.chunks_exact_mut(width)).enumerate() {
+        let element = match element_type {
+            // Converting from `u8` keeps the value only if it is already in
+            // range.
+            ElementType::U8Clamped => n
+                .to_element(ElementType::U8)
+                .and_then(|e| e.convert(ElementType::U8Clamped)),
+            _ => n.to_element(element_type),
+        };
+        element.ok_or(i)?.write_into(endianness, chunk);
+    }
+    // Length is always a multiple of the width here, so `new` cannot fail.
+    Ok(TypedArray::new(element_type, endianness, bytes).expect("whole elements"))
+}
+
+struct HumanVisitor;
+
+impl HumanVisitor {
+    fn build<E: de::Error>(
+        element_type: Option<ElementType>,
+        endianness: Option<Endianness>,
+        values: Option<Vec<Value>>,
+    ) -> Result<OwnedTypedArray, E> {
+        let element_type = element_type.ok_or_else(|| E::missing_field("type"))?;
+        let endianness = endianness.ok_or_else(|| E::missing_field("endianness"))?;
+        let values = values.ok_or_else(|| E::missing_field("values"))?;
+        array_from_values(element_type, endianness, &values).map_err(|i| {
+            E::custom(format_args!(
+                "value {i} is not a valid {}",
+                element_type.name()
+            ))
+        })
+    }
+}
+
+impl<'de> de::Visitor<'de> for HumanVisitor {
+    type Value = OwnedTypedArray;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a typed array with type, endianness and values")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let element_type = seq.next_element()?;
+        let endianness = seq.next_element()?;
+        let values = seq.next_element()?;
+        Self::build(element_type, endianness, values)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut element_type, mut endianness, mut values) = (None, None, None);
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Type => element_type = Some(map.next_value()?),
+                Field::Endianness => endianness = Some(map.next_value()?),
+                Field::Values => values = Some(map.next_value()?),
+            }
+        }
+        Self::build(element_type, endianness, values)
+    }
+}
+
+enum Field {
+    Type,
+    Endianness,
+    Values,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a typed array field")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Field, E> {
+                match v {
+                    "type" => Ok(Field::Type),
+                    "endianness" => Ok(Field::Endianness),
+                    "values" => Ok(Field::Values),
+                    _ => Err(E::unknown_field(v, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(Visitor)
+    }
+}
+
+struct TagVisitor;
+
+impl<'de> de::Visitor<'de> for TagVisitor {
+    type Value = OwnedTypedArray;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an RFC8746 typed-array tag and byte string")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (tagged, variant) = data.variant::<TagVariant>()?;
+        if !tagged.0 {
+            return Err(de::Error::custom("expected a typed-array tag"));
+        }
+        variant.tuple_variant(2, self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let tag: u64 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        #[cfg(feature = "half")]
+        if tag == BF16_MARKER_TAG {
+            let inner: OwnedTypedArray = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            if inner.element_type() != ElementType::U16 {
+                return Err(de::Error::custom("bf16 marker must wrap a u16 typed array"));
+            }
+            let endianness = inner.endianness();
+            return TypedArray::new(ElementType::Bf16, endianness, inner.into_bytes())
+                .map_err(de::Error::custom);
+        }
+        let bytes: ByteBuf = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let (element_type, endianness) =
+            element_type_from_tag(Tag::new(tag)).map_err(de::Error::custom)?;
+        TypedArray::new(element_type, endianness, bytes.0).map_err(de::Error::custom)
+    }
+}
+
+/// Which variant of the tag enum is present: `true` for tagged.
+struct TagVariant(bool);
+
+impl<'de> Deserialize<'de> for TagVariant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = TagVariant;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a tag variant")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<TagVariant, E> {
+                match v {
+                    0 => Ok(TagVariant(false)),
+                    1 => Ok(TagVariant(true)),
+                    _ => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<TagVariant, E> {
+                match v {
+                    UNTAGGED => Ok(TagVariant(false)),
+                    TAGGED => Ok(TagVariant(true)),
+                    _ => Err(E::unknown_variant(v, TAG_VARIANTS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_convert_like_elements() {
+        let values = [Value::Int(255), Value::Float(-2.0), Value::Float(1.5)];
+        let a = array_from_values(ElementType::F32, Endianness::Big, &values).unwrap();
+        assert_eq!(a.to_vec::<f32>().unwrap(), [255.0, -2.0, 1.5]);
+        assert_eq!(
+            array_from_values(ElementType::I32, Endianness::Big, &values),
+            Err(2)
+        );
+        // `u8clamped` input is checked, not clamped.
+        assert_eq!(
+            array_from_values(ElementType::U8Clamped, Endianness::Big, &values),
+            Err(1)
+        );
+        let a = array_from_values(ElementType::U8Clamped, Endianness::Big, &values[..1]).unwrap();
+        assert_eq!(a.as_bytes(), [255]);
+    }
+
+    #[test]
+    fn array_from_values_reports_index() {
+        let values = [Value::Int(1), Value::Int(-1)];
+        assert_eq!(
+            array_from_values(ElementType::U8, Endianness::Big, &values),
+            Err(1)
+        );
+        let a = array_from_values(ElementType::I16, Endianness::Little, &values).unwrap();
+        assert_eq!(a, TypedArray::from_slice(&[1i16, -1], Endianness::Little));
+    }
+}
@@ -1,6 +1,111 @@
 use crate::element::{ElementType, Endianness};
 use minicbor::data::{IanaTag, Tag};
 use minicbor::decode::Error;
+use minicbor::encode::{self, Write};
+use minicbor::Encoder;
+
+/// Default tag marking a `bf16` typed array: `"bf16"` in ASCII.
+///
+/// It lies in the first-come-first-served range but is not registered with
+/// IANA; pick a different tag with [`Bf16Encoding`] if it collides.
+pub const BF16_MARKER_TAG: u64 = 0x6266_3136;
+
+/// How [`ElementType::Bf16`] arrays are tagged, RFC8746 having no `bf16` tag.
+///
+/// [`TypedArray`](crate::TypedArray)'s `Encode`/`Decode` impls, the stream
+/// readers and [`crate::locate`] all use the default,
+/// `Marker(BF16_MARKER_TAG)`. Other encodings go through
+/// [`TypedArray::encode_with`](crate::TypedArray::encode_with) and
+/// [`TypedArray::decode_with`](crate::TypedArray::decode_with).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bf16Encoding {
+    /// Wrap an RFC8746 `u16` typed array in this tag. Decoders unaware of the
+    /// marker still see the raw bits as a `u16` array.
+    Marker(u64),
+    /// Use a dedicated tag per byte order in place of a typed-array tag.
+    Tags { big: u64, little: u64 },
+}
+
+impl Default for Bf16Encoding {
+    fn default() -> Self {
+        Bf16Encoding::Marker(BF16_MARKER_TAG)
+    }
+}
+
+/// Write the tag, or tags, in front of a typed array's byte string.
+pub(crate) fn encode_tag<W: Write>(
+    e: &mut Encoder<W>,
+    element_type: ElementType,
+    endianness: Endianness,
+    bf16: Bf16Encoding,
+) -> Result<(), encode::Error<W::Error>> {
+    #[cfg(feature = "half")]
+    if element_type == ElementType::Bf16 {
+        match (bf16, endianness) {
+            (Bf16Encoding::Marker(marker), _) => {
+                e.tag(Tag::new(marker))?;
+            }
+            (Bf16Encoding::Tags { big, .. }, Endianness::Big) => {
+                e.tag(Tag::new(big))?;
+                return Ok(());
+            }
+            (Bf16Encoding::Tags { little, .. }, Endianness::Little) => {
+                e.tag(Tag::new(little))?;
+                return Ok(());
+            }
+        }
+    }
+    #[cfg(not(feature = "half"))]
+    let _ = bf16;
+    e.tag(element_type.tag(endianness))?;
+    Ok(())
+}
+
+/// Resolves the tag(s) in front of a typed array's byte string, one at a time.
+#[cfg_attr(not(feature = "half"), allow(dead_code))]
+pub(crate) struct TagResolver {
+    bf16: Bf16Encoding,
+    marked: bool,
+}
+
+impl TagResolver {
+    pub(crate) fn new(bf16: Bf16Encoding) -> Self {
+        TagResolver {
+            bf16,
+            marked: false,
+        }
+    }
+
+    /// Feed the next tag. Returns `None` after a `bf16` marker, whose `u16`
+    /// typed-array tag must follow.
+    pub(crate) fn feed(&mut self, tag: Tag) -> Result<Option<(ElementType, Endianness)>, Error> {
+        #[cfg(feature = "half")]
+        if !self.marked {
+            match self.bf16 {
+                Bf16Encoding::Marker(marker) if tag.as_u64() == marker => {
+                    self.marked = true;
+                    return Ok(None);
+                }
+                Bf16Encoding::Tags { big, .. } if tag.as_u64() == big => {
+                    return Ok(Some((ElementType::Bf16, Endianness::Big)));
+                }
+                Bf16Encoding::Tags { little, .. } if tag.as_u64() == little => {
+                    return Ok(Some((ElementType::Bf16, Endianness::Little)));
+                }
+                _ => {}
+            }
+        }
+        let (element_type, endianness) = element_type_from_tag(tag)?;
+        #[cfg(feature = "half")]
+        if self.marked {
+            if element_type != ElementType::U16 {
+                return Err(Error::message("bf16 marker must wrap a u16 typed array"));
+            }
+            return Ok(Some((ElementType::Bf16, endianness)));
+        }
+        Ok(Some((element_type, endianness)))
+    }
+}
 
 /// Map a CBOR tag to a typed-array element type and endianness.
 ///
@@ -86,6 +191,35 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "half")]
+    #[test]
+    fn bf16_marker_wraps_u16_tag() {
+        let mut r = TagResolver::new(Bf16Encoding::default());
+        assert_eq!(r.feed(Tag::new(BF16_MARKER_TAG)).unwrap(), None);
+        let u16_le = ElementType::U16.tag(Endianness::Little).tag();
+        assert_eq!(
+            r.feed(u16_le).unwrap(),
+            Some((ElementType::Bf16, Endianness::Little))
+        );
+
+        let mut r = TagResolver::new(Bf16Encoding::default());
+        r.feed(Tag::new(BF16_MARKER_TAG)).unwrap();
+        assert!(r.feed(ElementType::U32.tag(Endianness::Big).tag()).is_err());
+
+        let tags = Bf16Encoding::Tags {
+            big: 500,
+            little: 501,
+        };
+        let mut r = TagResolver::new(tags);
+        assert_eq!(
+            r.feed(Tag::new(501)).unwrap(),
+            Some((ElementType::Bf16, Endianness::Little))
+        );
+        assert!(TagResolver::new(tags)
+            .feed(Tag::new(BF16_MARKER_TAG))
+            .is_err());
+    }
+
     #[test]
     fn non_typed_array_tag_errors() {
         assert!(element_type_from_tag(Tag::new(0)).is_err()); // DateTime
@@ -0,0 +1,239 @@
+use crate::convert::ConvertError;
+use crate::element::{ElementType, Endianness};
+use crate::multi_dim::InvalidShape;
+use crate::typed_array::{OwnedTypedArray, TypedArray};
+use crate::walk::{Found, TypedArrays};
+use core::fmt;
+use minicbor::encode::Write;
+use minicbor::{decode, encode, Decoder, Encoder};
+
+/// Error from [`transcode`].
+#[derive(Debug)]
+pub enum TranscodeError<W, E> {
+    Decode(decode::Error),
+    Encode(encode::Error<W>),
+    /// The callback failed.
+    Rewrite(E),
+    /// The replacement for a multi-dimensional array's payload does not have
+    /// `len` elements like the original.
+    Shape(InvalidShape),
+}
+
+impl<W: fmt::Display, E: fmt::Display> fmt::Display for TranscodeError<W, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranscodeError::Decode(e) => write!(f, "{e}"),
+            TranscodeError::Encode(e) => write!(f, "{e}"),
+            TranscodeError::Rewrite(e) => write!(f, "{e}"),
+            TranscodeError::Shape(e) => write!(f, "replacement payload: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W, E> std::error::Error for TranscodeError<W, E>
+where
+    W: fmt::Debug + fmt::Display,
+    E: fmt::Debug + fmt::Display,
+{
+}
+
+/// Copy the CBOR item at the decoder's position to `e`, passing each typed
+/// array it holds to `rewrite` and writing the array returned in its place.
+///
+/// Arrays for which `rewrite` returns `None`, and every other byte of the
+/// item, are copied unchanged. Typed arrays are found as [`TypedArrays`]
+/// finds them; a multi-dimensional array keeps its tag and shape, so its
+/// replacement payload must have as many elements as the original.
+///
+/// Output is written as the item is read, one typed array at a time.
+pub fn transcode<'b, W, E, F>(
+    d: &mut Decoder<'b>,
+    e: &mut Encoder<W>,
+    mut rewrite: F,
+) -> Result<(), TranscodeError<W::Error, E>>
+where
+    W: Write,
+    F: FnMut(&Found<'b>) -> Result<Option<OwnedTypedArray>, E>,
+{
+    let input = d.input();
+    let mut copied = d.position();
+    for found in TypedArrays::new(d) {
+        let found = found.map_err(TranscodeError::Decode)?;
+        let Some(replacement) = rewrite(&found).map_err(TranscodeError::Rewrite)? else {
+            continue;
+        };
+        if found.shape.is_some() && replacement.len() != found.array.len() {
+            return Err(TranscodeError::Shape(InvalidShape {
+                len: replacement.len(),
+            }));
+        }
+        copy(e, &input[copied..found.offset])?;
+        e.encode(&replacement).map_err(TranscodeError::Encode)?;
+        copied = found.end;
+    }
+    copy(e, &input[copied..d.position()])
+}
+
+fn copy<W: Write, E>(e: &mut Encoder<W>, bytes: &[u8]) -> Result<(), TranscodeError<W::Error, E>> {
+    e.writer_mut()
+        .write_all(bytes)
+        .map_err(|err| TranscodeError::Encode(encode::Error::write(err)))
+}
+
+/// A [`transcode`] callback giving every typed array one element type, byte
+/// order, or both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Normalize {
+    /// Element type to convert to with [`crate::Element::convert`]; `None`
+    /// keeps each array's own.
+    pub element_type: Option<ElementType>,
+    /// Byte order to lay elements out in; `None` keeps each array's own.
+    pub endianness: Option<Endianness>,
+}
+
+impl Normalize {
+    /// `array` rewritten to this policy, or `None` if it already conforms.
+    pub fn apply<C: AsRef<[u8]>>(
+        &self,
+        array: &TypedArray<C>,
+    ) -> Result<Option<OwnedTypedArray>, ConvertError> {
+        let element_type = self.element_type.unwrap_or(array.element_type());
+        let endianness = self.endianness.unwrap_or(array.endianness());
+        let conforms = element_type == array.element_type()
+            && (endianness == array.endianness() || element_type.width() == 1);
+        if conforms {
+            return Ok(None);
+        }
+        array.convert(element_type, endianness).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MultiDimArray, MultiDimArrayRef, Order, OwnedMultiDimArray};
+    use alloc::vec::Vec;
+    use minicbor::data::Tag;
+    use std::string::ToString;
+
+    fn run(
+        input: &[u8],
+        policy: Normalize,
+    ) -> Result<Vec<u8>, TranscodeError<core::convert::Infallible, ConvertError>> {
+        let mut d = Decoder::new(input);
+        let mut out = Vec::new();
+        transcode(&mut d, &mut Encoder::new(&mut out), |found| {
+            policy.apply(&found.array)
+        })?;
+        assert_eq!(d.position(), input.len());
+        Ok(out)
+    }
+
+    #[test]
+    fn rewrites_arrays_and_copies_the_rest() {
+        let big = TypedArray::from_slice(&[1u32, 70_000], Endianness::Big);
+        let little = TypedArray::from_slice(&[2.5f64], Endianness::Little);
+        let mut input = Vec::new();
+        let mut e = Encoder::new(&mut input);
+        // An indefinite-length map and an unrelated tag survive as they are.
+        e.begin_map()
+            .unwrap()
+            .str("a")
+            .unwrap()
+            .encode(&big)
+            .unwrap();
+        e.str("b")
+            .unwrap()
+            .tag(Tag::new(1))
+            .unwrap()
+            .f64(0.5)
+            .unwrap();
+        e.str("c").unwrap().encode(&little).unwrap().end().unwrap();
+
+        let to_little = Normalize {
+            endianness: Some(Endianness::Little),
+            ..Normalize::default()
+        };
+        let out = run(&input, to_little).unwrap();
+        let mut expected = Vec::new();
+        let mut e = Encoder::new(&mut expected);
+        e.begin_map().unwrap().str("a").unwrap();
+        e.encode(big.convert(ElementType::U32, Endianness::Little).unwrap())
+            .unwrap();
+        e.str("b")
+            .unwrap()
+            .tag(Tag::new(1))
+            .unwrap()
+            .f64(0.5)
+            .unwrap();
+        e.str("c").unwrap().encode(&little).unwrap().end().unwrap();
+        assert_eq!(out, expected);
+
+        assert_eq!(run(&input, Normalize::default()).unwrap(), input);
+    }
+
+    #[test]
+    fn converts_element_types_inside_multi_dim_arrays() {
+        let m = MultiDimArray::new(
+            alloc::vec![2],
+            Order::RowMajor,
+            TypedArray::from_slice(&[1i16, -2], Endianness::Big),
+        )
+        .unwrap();
+        let mut input = Vec::new();
+        minicbor::encode(&m, &mut input).unwrap();
+        let policy = Normalize {
+            element_type: Some(ElementType::F32),
+            endianness: Some(Endianness::Little),
+        };
+        let out = run(&input, policy).unwrap();
+        let back: MultiDimArrayRef = minicbor::decode(&out).unwrap();
+        assert_eq!(back.shape(), [2]);
+        assert_eq!(back.array().to_vec::<f32>().unwrap(), [1.0, -2.0]);
+
+        let policy = Normalize {
+            element_type: Some(ElementType::U8),
+            ..Normalize::default()
+        };
+        assert!(matches!(
+            run(&input, policy),
+            Err(TranscodeError::Rewrite(ConvertError::Value {
+                index: 1,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn callbacks_see_paths_and_keep_shapes() {
+        let a = TypedArray::from_slice(&[1u8, 2], Endianness::Big);
+        let m = MultiDimArray::new(alloc::vec![1, 2], Order::ColumnMajor, a.clone()).unwrap();
+        let mut input = Vec::new();
+        minicbor::encode((&a, &m), &mut input).unwrap();
+        let one = || TypedArray::from_slice(&[9u8], Endianness::Big);
+
+        let mut d = Decoder::new(&input);
+        let mut out = Vec::new();
+        let mut seen = Vec::new();
+        transcode(&mut d, &mut Encoder::new(&mut out), |found| {
+            seen.push(found.path.to_string());
+            Ok::<_, ()>(found.shape.is_none().then(one))
+        })
+        .unwrap();
+        assert_eq!(seen, ["[0]", "[1]"]);
+        let (first, second): (OwnedTypedArray, OwnedMultiDimArray) =
+            minicbor::decode(&out).unwrap();
+        assert_eq!(first, one());
+        assert_eq!(second, m);
+
+        let mut d = Decoder::new(&input);
+        let r = transcode(&mut d, &mut Encoder::new(Vec::new()), |_| {
+            Ok::<_, ()>(Some(one()))
+        });
+        assert!(matches!(
+            r,
+            Err(TranscodeError::Shape(InvalidShape { len: 1 }))
+        ));
+    }
+}
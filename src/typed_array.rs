@@ -1,6 +1,6 @@
 #[cfg(feature = "alloc")]
-use crate::element::Scalar;
-use crate::element::{Element, ElementType, Endianness};
+use crate::convert::ConvertError;
+use crate::element::{Element, ElementType, Endianness, Scalar};
 use core::fmt;
 
 /// Error returned by [`TypedArray::new`] when the byte payload length is not a
@@ -24,13 +24,34 @@ impl fmt::Display for InvalidLength {
 #[cfg(feature = "std")]
 impl std::error::Error for InvalidLength {}
 
+/// Error returned by [`TypedArray::write_into`] when the caller's buffer cannot
+/// hold the laid-out values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BufferTooSmall {
+    pub needed: usize,
+    pub available: usize,
+}
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "buffer of {} bytes is too small, {} bytes needed",
+            self.available, self.needed
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferTooSmall {}
+
 /// An RFC8746 typed array: a homogeneous numeric array stored as its raw byte
 /// payload plus an element type and endianness.
 ///
 /// Generic over the byte storage `C`:
 /// - [`TypedArrayRef`] (`&[u8]`) borrows the payload — no allocator required.
 /// - [`OwnedTypedArray`] (`Vec<u8>`) owns it (requires the `alloc` feature).
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct TypedArray<C> {
     element_type: ElementType,
     endianness: Endianness,
@@ -88,6 +109,11 @@ impl<C: AsRef<[u8]>> TypedArray<C> {
         self.bytes.as_ref()
     }
 
+    /// Give back the byte payload storage.
+    pub fn into_bytes(self) -> C {
+        self.bytes
+    }
+
     /// Number of elements.
     pub fn len(&self) -> usize {
         self.bytes.as_ref().len() / self.element_type.width()
@@ -106,6 +132,109 @@ impl<C: AsRef<[u8]>> TypedArray<C> {
             pos: 0,
         }
     }
+
+    /// Zero-copy view of the payload as `&[T]`.
+    ///
+    /// Returns `None` unless `T` backs the element type, the payload is in
+    /// [`Endianness::NATIVE`] byte order (or single-byte), and the payload is
+    /// aligned for `T`. Storage such as [`crate::AlignedBytes`] guarantees the
+    /// last condition.
+    pub fn as_native_slice<T: Scalar>(&self) -> Option<&[T]> {
+        if !self.is_native::<T>() {
+            return None;
+        }
+        // SAFETY: `Scalar` is sealed to plain-old-data types valid for any bit
+        // pattern, so reinterpreting initialized bytes as `T` is sound.
+        let (head, values, tail) = unsafe { self.bytes.as_ref().align_to::<T>() };
+        (head.is_empty() && tail.is_empty()).then_some(values)
+    }
+
+    /// Decode every element into a `Vec<T>`.
+    ///
+    /// `T` must back the element type.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec<T: Scalar>(&self) -> Result<alloc::vec::Vec<T>, ConvertError> {
+        ConvertError::check::<T>(self.element_type)?;
+        let width = self.element_type.width();
+        let chunks = self.bytes.as_ref().chunks_exact(width);
+        Ok(match self.endianness {
+            Endianness::Big => chunks.map(T::read_be).collect(),
+            Endianness::Little => chunks.map(T::read_le).collect(),
+        })
+    }
+
+    /// Convert every element to `element_type` with [`Element::convert`],
+    /// laid out in `endianness`.
+    #[cfg(feature = "alloc")]
+    pub fn convert(
+        &self,
+        element_type: ElementType,
+        endianness: Endianness,
+    ) -> Result<OwnedTypedArray, ConvertError> {
+        let width = element_type.width();
+        let mut bytes = alloc::vec![0; self.len() * width];
+        for (index, (element, out)) in self.iter().zip(bytes.chunks_exact_mut(width)).enumerate() {
+            element
+                .convert(element_type)
+                .ok_or(ConvertError::Value {
+                    index,
+                    requested: element_type,
+                })?
+                .write_into(endianness, out);
+        }
+        Ok(TypedArray::new(element_type, endianness, bytes).expect("whole elements"))
+    }
+
+    fn is_native<T: Scalar>(&self) -> bool {
+        self.element_type.is_backed_by::<T>()
+            && (self.endianness == Endianness::NATIVE || self.element_type.width() == 1)
+    }
+}
+
+impl<C: AsRef<[u8]> + AsMut<[u8]>> TypedArray<C> {
+    /// Zero-copy mutable view of the payload as `&mut [T]`.
+    ///
+    /// Subject to the same conditions as [`TypedArray::as_native_slice`].
+    pub fn as_native_mut_slice<T: Scalar>(&mut self) -> Option<&mut [T]> {
+        if !self.is_native::<T>() {
+            return None;
+        }
+        // SAFETY: as in `as_native_slice`; every bit pattern written through
+        // the view is also a valid byte payload.
+        let (head, values, tail) = unsafe { self.bytes.as_mut().align_to_mut::<T>() };
+        (head.is_empty() && tail.is_empty()).then_some(values)
+    }
+}
+
+impl<'b> TypedArray<&'b [u8]> {
+    /// Lay out native scalar values into a caller-provided buffer (e.g. a stack
+    /// or static array) and borrow the result. Needs no allocator.
+    ///
+    /// Only the first `size_of_val(values)` bytes of `buf` are used.
+    pub fn write_into<T: Scalar>(
+        values: &[T],
+        endianness: Endianness,
+        buf: &'b mut [u8],
+    ) -> Result<Self, BufferTooSmall> {
+        let needed = core::mem::size_of_val(values);
+        if buf.len() < needed {
+            return Err(BufferTooSmall {
+                needed,
+                available: buf.len(),
+            });
+        }
+        let bytes = &mut buf[..needed];
+        let width = T::ELEMENT_TYPE.width();
+        for (&v, chunk) in values.iter().zip(bytes.chunks_exact_mut(width)) {
+            match endianness {
+                Endianness::Big => v.write_be_into(chunk),
+                Endianness::Little => v.write_le_into(chunk),
+            }
+        }
+        // Length is always a multiple of the width here, so `new` cannot fail.
+        Ok(TypedArray::new(T::ELEMENT_TYPE, endianness, &*bytes)
+            .expect("write_into produces valid length"))
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -165,6 +294,42 @@ impl<'a, C: AsRef<[u8]>> IntoIterator for &'a TypedArray<C> {
     }
 }
 
+/// How many values [`TypedArray`]'s `Display` and `Debug` show before `…`.
+const PREVIEW: usize = 8;
+
+/// Shows the type, endianness and values, as in
+/// `f32le[1.0, 2.5, -3.25, … (997 more)]`.
+///
+/// Only the first few values are shown unless the alternate flag (`{:#}`) is
+/// set. Precision, width, fill and alignment apply to each value.
+impl<C: AsRef<[u8]>> fmt::Display for TypedArray<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.element_type.name())?;
+        if self.element_type.width() > 1 {
+            f.write_str(self.endianness.name())?;
+        }
+        f.write_str("[")?;
+        let shown = if f.alternate() { self.len() } else { PREVIEW };
+        for (i, element) in self.iter().take(shown).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            fmt::Display::fmt(&element, f)?;
+        }
+        if self.len() > shown {
+            write!(f, ", … ({} more)", self.len() - shown)?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Same as `Display`: decoded values rather than the raw payload.
+impl<C: AsRef<[u8]>> fmt::Debug for TypedArray<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +346,26 @@ mod tests {
         assert_eq!(a.endianness(), Endianness::Big);
     }
 
+    #[test]
+    fn display_shows_a_bounded_prefix() {
+        let values: std::vec::Vec<f32> = (0..1000).map(|i| i as f32 * 0.5).collect();
+        let a = TypedArray::from_slice(&values, Endianness::Little);
+        assert_eq!(
+            std::format!("{a}"),
+            "f32le[0.0, 0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 3.5, … (992 more)]"
+        );
+        assert_eq!(std::format!("{a:?}"), std::format!("{a}"));
+        assert_eq!(std::format!("{a:#}").matches(", ").count(), 999);
+
+        let b = TypedArray::from_slice(&[1.0f64, -3.25], Endianness::Big);
+        assert_eq!(std::format!("{b:.1}"), "f64be[1.0, -3.2]");
+        assert_eq!(std::format!("{b:>6}"), "f64be[   1.0,  -3.25]");
+        let c = TypedArray::from_slice(&[-1i8, 7], Endianness::Little);
+        assert_eq!(std::format!("{c:03}"), "i8[-01, 007]");
+        let empty = TypedArray::from_slice::<u16>(&[], Endianness::Big);
+        assert_eq!(std::format!("{empty}"), "u16be[]");
+    }
+
     #[test]
     fn len_and_empty() {
         let a = TypedArray::new(ElementType::U32, Endianness::Big, &[0u8; 8][..]).unwrap();
@@ -204,6 +389,42 @@ mod tests {
         assert_eq!(a.iter().len(), 2);
     }
 
+    #[test]
+    fn write_into_lays_out_values() {
+        let mut buf = [0xffu8; 8];
+        let a = TypedArray::write_into(&[0x1234u16, 0x0001], Endianness::Big, &mut buf).unwrap();
+        assert_eq!(a.as_bytes(), &[0x12, 0x34, 0x00, 0x01]);
+        assert_eq!(a.element_type(), ElementType::U16);
+        assert_eq!(a.len(), 2);
+    }
+
+    #[test]
+    fn write_into_rejects_short_buffer() {
+        let mut buf = [0u8; 7];
+        let err = TypedArray::write_into(&[1.0f64], Endianness::Little, &mut buf).unwrap_err();
+        assert_eq!(
+            err,
+            BufferTooSmall {
+                needed: 8,
+                available: 7
+            }
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn to_vec_checks_element_type() {
+        let a = TypedArray::from_slice(&[1u16, 0xff00], Endianness::Little);
+        assert_eq!(a.to_vec::<u16>(), Ok(alloc::vec![1, 0xff00]));
+        assert_eq!(
+            a.to_vec::<i16>(),
+            Err(ConvertError::ElementType {
+                array: ElementType::U16,
+                requested: ElementType::I16
+            })
+        );
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn from_slice_round_trips_through_iter() {
@@ -0,0 +1,397 @@
+use crate::multi_dim::{MultiDimArrayRef, Order};
+use crate::typed_array::TypedArrayRef;
+use alloc::vec::Vec;
+use core::fmt;
+use minicbor::data::Type;
+use minicbor::decode::Error;
+use minicbor::Decoder;
+
+/// Maximum container nesting depth [`TypedArrays`], and so
+/// [`crate::locate`], descends into.
+pub(crate) const MAX_DEPTH: usize = 256;
+
+/// One step from a CBOR item into a nested one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment<'b> {
+    /// The value under a text map key.
+    Key(&'b str),
+    /// The value under an integer map key.
+    IntKey(i128),
+    /// The value under any other map key, by the entry's position in the map.
+    OtherKey(u64),
+    /// An array item.
+    Index(u64),
+}
+
+/// Where a typed array sits in a CBOR item, from the outside in.
+///
+/// Displays jq-style, as in `.samples[1].7`; the item itself is the empty
+/// path.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Path<'b> {
+    segments: Vec<PathSegment<'b>>,
+}
+
+impl<'b> Path<'b> {
+    pub fn segments(&self) -> &[PathSegment<'b>] {
+        &self.segments
+    }
+}
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                PathSegment::Key(key) => write!(f, ".{key}")?,
+                PathSegment::IntKey(key) => write!(f, ".{key}")?,
+                PathSegment::OtherKey(entry) => write!(f, ".<{entry}>")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A typed array found by [`TypedArrays`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Found<'b> {
+    pub path: Path<'b>,
+    /// Borrowed from the decoder's input.
+    pub array: TypedArrayRef<'b>,
+    /// Shape and order if `array` is the payload of a tag 40/1040
+    /// multi-dimensional array at `path`.
+    pub shape: Option<(Vec<usize>, Order)>,
+    /// Byte offset of the typed array's first tag in the decoder's input.
+    /// For a multi-dimensional array this is the payload's tag, after the
+    /// shape.
+    pub offset: usize,
+    /// Byte offset just past the end of the typed array's payload.
+    pub end: usize,
+}
+
+/// An open array or map.
+struct Frame {
+    map: bool,
+    /// Number of items or entries; `None` if indefinite.
+    len: Option<u64>,
+    next: u64,
+}
+
+/// Iterator over every typed array in one CBOR item, in document order.
+///
+/// Descends into arrays, map values and unrelated tags, and reports typed
+/// arrays whether tagged directly, wrapped in a `bf16` marker, or held in a
+/// multi-dimensional array. Map keys are only used for paths; typed arrays
+/// inside them are not reported. Typed arrays with indefinite-length
+/// payloads cannot be borrowed and are skipped.
+///
+/// When iteration ends the decoder is positioned after the item. After an
+/// error, iteration stops.
+pub struct TypedArrays<'d, 'b> {
+    decoder: &'d mut Decoder<'b>,
+    path: Vec<PathSegment<'b>>,
+    stack: Vec<Frame>,
+    started: bool,
+    failed: bool,
+}
+
+impl<'d, 'b> TypedArrays<'d, 'b> {
+    /// Walk the item at the decoder's position.
+    pub fn new(decoder: &'d mut Decoder<'b>) -> Self {
+        TypedArrays {
+            decoder,
+            path: Vec::new(),
+            stack: Vec::new(),
+            started: false,
+            failed: false,
+        }
+    }
+
+    fn step(&mut self) -> Result<Option<Found<'b>>, Error> {
+        loop {
+            if self.started {
+                let d = &mut *self.decoder;
+                let Some(frame) = self.stack.last_mut() else {
+                    return Ok(None);
+                };
+                let more = match frame.len {
+                    Some(n) => frame.next < n,
+                    None => d.datatype()? != Type::Break,
+                };
+                if !more {
+                    if frame.len.is_none() {
+                        d.skip()?;
+                    }
+                    self.stack.pop();
+                    // Leave the container's own path segment, if it has one.
+                    if !self.stack.is_empty() {
+                        self.path.pop();
+                    }
+                    continue;
+                }
+                let entry = frame.next;
+                frame.next += 1;
+                let segment = if frame.map {
+                    key(d, entry)?
+                } else {
+                    PathSegment::Index(entry)
+                };
+                self.path.push(segment);
+            }
+            self.started = true;
+            if self.stack.len() > MAX_DEPTH {
+                return Err(Error::message("CBOR nesting too deep"));
+            }
+
+            let nested = !self.stack.is_empty();
+            match self.visit()? {
+                Visited::Opened => {}
+                Visited::Done => {
+                    if nested {
+                        self.path.pop();
+                    }
+                }
+                Visited::Found(array, shape, offset) => {
+                    let found = Found {
+                        path: Path {
+                            segments: self.path.clone(),
+                        },
+                        array,
+                        shape,
+                        offset,
+                        end: self.decoder.position(),
+                    };
+                    if nested {
+                        self.path.pop();
+                    }
+                    return Ok(Some(found));
+                }
+            }
+        }
+    }
+
+    /// Consume the item at the decoder's position, or open it if it is an
+    /// array or map.
+    fn visit(&mut self) -> Result<Visited<'b>, Error> {
+        let d = &mut *self.decoder;
+        loop {
+            match d.datatype()? {
+                Type::Tag => {
+                    let start = d.position();
+                    if let Ok(array) = d.decode::<TypedArrayRef>() {
+                        return Ok(Visited::Found(array, None, start));
+                    }
+                    d.set_position(start);
+                    if Order::from_tag(d.tag()?).is_some() {
+                        d.set_position(start);
+                        if let Ok(m) = d.decode::<MultiDimArrayRef>() {
+                            // Find where the payload starts: past the tag,
+                            // the array header and the shape.
+                            let end = d.position();
+                            d.set_position(start);
+                            d.tag()?;
+                            d.array()?;
+                            d.skip()?;
+                            let offset = d.position();
+                            d.set_position(end);
+                            let (shape, order, array) = m.into_parts();
+                            return Ok(Visited::Found(array, Some((shape, order)), offset));
+                        }
+                        d.set_position(start);
+                        d.tag()?;
+                    }
+                    // An unrelated tag: visit what it wraps.
+                }
+                Type::Array | Type::ArrayIndef => {
+                    let len = d.array()?;
+                    self.stack.push(Frame {
+                        map: false,
+                        len,
+                        next: 0,
+                    });
+                    return Ok(Visited::Opened);
+                }
+                Type::Map | Type::MapIndef => {
+                    let len = d.map()?;
+                    self.stack.push(Frame {
+                        map: true,
+                        len,
+                        next: 0,
+                    });
+                    return Ok(Visited::Opened);
+                }
+                _ => {
+                    d.skip()?;
+                    return Ok(Visited::Done);
+                }
+            }
+        }
+    }
+}
+
+enum Visited<'b> {
+    /// The array, its shape and its offset.
+    Found(TypedArrayRef<'b>, Option<(Vec<usize>, Order)>, usize),
+    Done,
+    Opened,
+}
+
+/// Read a map key as the path segment for its value.
+fn key<'b>(d: &mut Decoder<'b>, entry: u64) -> Result<PathSegment<'b>, Error> {
+    Ok(match d.datatype()? {
+        Type::String => PathSegment::Key(d.str()?),
+        Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::I8
+        | Type::I16
+        | Type::I32
+        | Type::I64
+        | Type::Int => PathSegment::IntKey(d.int()?.into()),
+        _ => {
+            d.skip()?;
+            PathSegment::OtherKey(entry)
+        }
+    })
+}
+
+impl<'b> Iterator for TypedArrays<'_, 'b> {
+    type Item = Result<Found<'b>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.step() {
+            Ok(found) => found.map(Ok),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Endianness, MultiDimArray, TypedArray};
+    use minicbor::data::Tag;
+    use minicbor::Encoder;
+    use std::string::ToString;
+
+    fn paths(bytes: &[u8]) -> Vec<std::string::String> {
+        let mut d = Decoder::new(bytes);
+        let found: Vec<_> = TypedArrays::new(&mut d).collect::<Result<_, _>>().unwrap();
+        assert_eq!(d.position(), bytes.len());
+        found.iter().map(|f| f.path.to_string()).collect()
+    }
+
+    #[test]
+    fn reports_paths_and_borrows_payloads() {
+        let a = TypedArray::from_slice(&[1u16, 2], Endianness::Little);
+        let b = TypedArray::from_slice(&[1.5f32], Endianness::Big);
+        let mut buf = Vec::new();
+        let mut e = Encoder::new(&mut buf);
+        e.map(4).unwrap();
+        e.str("a").unwrap().encode(&a).unwrap();
+        e.str("list").unwrap().begin_array().unwrap();
+        e.str("skip")
+            .unwrap()
+            .tag(Tag::new(0))
+            .unwrap()
+            .str("x")
+            .unwrap();
+        e.map(1).unwrap().i8(-3).unwrap().encode(&b).unwrap();
+        e.end().unwrap();
+        e.bytes(b"k").unwrap().encode(&a).unwrap();
+        e.str("n").unwrap().null().unwrap();
+
+        assert_eq!(paths(&buf), [".a", ".list[2].-3", ".<2>"]);
+        let mut d = Decoder::new(&buf);
+        let found: Vec<_> = TypedArrays::new(&mut d).map(Result::unwrap).collect();
+        assert_eq!(found[1].array.to_vec::<f32>().unwrap(), [1.5]);
+        assert_eq!(
+            found[1].path.segments(),
+            [
+                PathSegment::Key("list"),
+                PathSegment::Index(2),
+                PathSegment::IntKey(-3)
+            ]
+        );
+        let encoded = minicbor::to_vec(&b).unwrap();
+        assert_eq!(&buf[found[1].offset..found[1].end], encoded);
+        let payload = found[0].array.as_bytes().as_ptr() as usize;
+        assert!((buf.as_ptr() as usize..buf.as_ptr() as usize + buf.len()).contains(&payload));
+    }
+
+    #[test]
+    fn recognizes_multi_dim_and_tagged_arrays() {
+        let m = MultiDimArray::new(
+            alloc::vec![2, 1],
+            Order::ColumnMajor,
+            TypedArray::from_slice(&[7i32, 8], Endianness::Big),
+        )
+        .unwrap();
+        let mut buf = Vec::new();
+        let mut e = Encoder::new(&mut buf);
+        e.array(3).unwrap();
+        e.tag(Tag::new(55799)).unwrap().encode(&m).unwrap();
+        // Tag 40 around something other than a multi-dimensional array.
+        e.tag(Order::RowMajor.tag()).unwrap().array(1).unwrap();
+        e.encode(TypedArray::from_slice(&[1u8], Endianness::Big))
+            .unwrap();
+        e.encode(TypedArray::from_slice::<u8>(&[], Endianness::Big))
+            .unwrap();
+
+        let mut d = Decoder::new(&buf);
+        let found: Vec<_> = TypedArrays::new(&mut d).map(Result::unwrap).collect();
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].path.to_string(), "[0]");
+        assert_eq!(
+            found[0].shape,
+            Some((alloc::vec![2, 1], Order::ColumnMajor))
+        );
+        assert_eq!(found[0].array.to_vec::<i32>().unwrap(), [7, 8]);
+        assert_eq!(found[1].path.to_string(), "[1][0]");
+        assert_eq!(found[1].shape, None);
+        assert!(found[2].array.is_empty());
+    }
+
+    #[test]
+    fn walks_one_item_of_a_sequence() {
+        let a = TypedArray::from_slice(&[1u8], Endianness::Big);
+        let mut buf = Vec::new();
+        let mut e = Encoder::new(&mut buf);
+        e.begin_map()
+            .unwrap()
+            .str("x")
+            .unwrap()
+            .encode(&a)
+            .unwrap()
+            .end()
+            .unwrap();
+        e.encode(&a).unwrap().u8(3).unwrap();
+
+        let mut d = Decoder::new(&buf);
+        assert_eq!(TypedArrays::new(&mut d).count(), 1);
+        let found: Vec<_> = TypedArrays::new(&mut d).map(Result::unwrap).collect();
+        assert_eq!(found[0].path, Path::default());
+        assert_eq!(TypedArrays::new(&mut d).count(), 0);
+        assert_eq!(d.position(), buf.len());
+    }
+
+    #[test]
+    fn stops_after_an_error() {
+        let deep = alloc::vec![0x81; MAX_DEPTH + 2];
+        let mut d = Decoder::new(&deep);
+        let mut walk = TypedArrays::new(&mut d);
+        assert!(walk.next().unwrap().is_err());
+        assert!(walk.next().is_none());
+
+        let truncated = [0x82, 0x01];
+        let mut d = Decoder::new(&truncated);
+        assert!(TypedArrays::new(&mut d).any(|r| r.is_err()));
+    }
+}
@@ -0,0 +1,150 @@
+#![cfg(feature = "cli")]
+
+use minicbor::Encoder;
+use minicbor_typed_arrays::{Endianness, MultiDimArray, Order, TypedArray};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn cbor_ta(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cbor-ta"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// A two-item CBOR sequence: a map holding arrays at nested paths, then a
+/// bare typed array.
+fn sample() -> Vec<u8> {
+    let values: Vec<i32> = (1..=20).collect();
+    let image = MultiDimArray::new(
+        vec![2, 2],
+        Order::RowMajor,
+        TypedArray::from_slice(&[1u8, 2, 3, 4], Endianness::Big),
+    )
+    .unwrap();
+    let mut buf = Vec::new();
+    let mut e = Encoder::new(&mut buf);
+    e.map(3).unwrap();
+    e.str("samples").unwrap();
+    e.array(2).unwrap();
+    e.str("skipped").unwrap();
+    e.encode(TypedArray::from_slice(&values, Endianness::Little))
+        .unwrap();
+    e.u8(7).unwrap().encode(&image).unwrap();
+    e.str("empty").unwrap();
+    e.encode(TypedArray::from_slice::<f64>(&[], Endianness::Big))
+        .unwrap();
+    e.encode(TypedArray::from_slice(
+        &[0.5f32, -1.5, f32::NAN],
+        Endianness::Big,
+    ))
+    .unwrap();
+    buf
+}
+
+#[test]
+fn lists_arrays_with_paths_and_stats() {
+    let out = cbor_ta(&["-n", "3", "-"], &sample());
+    assert!(out.status.success());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "#0.samples[1]: i32 le len=20 min=1 max=20 mean=10.5",
+            "  [1, 2, 3, … (17 more)]",
+            "#0.7: u8 be shape=[2, 2] len=4 min=1 max=4 mean=2.5",
+            "  [1, 2, 3, … (1 more)]",
+            "#0.empty: f64 be len=0 min=- max=- mean=-",
+            "  []",
+            "#1: f32 be len=3 min=-1.5 max=0.5 mean=-0.5",
+            "  [0.5, -1.5, NaN]",
+        ]
+    );
+}
+
+#[test]
+fn zero_values_lists_only_the_count() {
+    let out = cbor_ta(&["-n", "0", "-"], &sample());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let values: Vec<&str> = stdout.lines().skip(1).step_by(2).collect();
+    assert_eq!(
+        values,
+        [
+            "  [… (20 more)]",
+            "  [… (4 more)]",
+            "  []",
+            "  [… (3 more)]"
+        ]
+    );
+}
+
+#[test]
+fn dump_prints_every_value() {
+    let out = cbor_ta(&["--dump", "-"], &sample());
+    let stdout = String::from_utf8(out.stdout).unwrap();
+    let all: Vec<String> = (1..=20).map(|v| v.to_string()).collect();
+    assert!(stdout.contains(&format!("[{}]", all.join(", "))));
+    assert!(!stdout.contains('…'));
+}
+
+#[test]
+fn reports_malformed_input() {
+    let single = cbor_ta(&["-"], &sample()[..sample().len() - 15]);
+    assert!(single.status.success());
+
+    let mut input = sample();
+    input.truncate(input.len() - 3);
+    let out = cbor_ta(&["-"], &input);
+    assert!(!out.status.success());
+    assert!(String::from_utf8(out.stderr)
+        .unwrap()
+        .starts_with("cbor-ta: -: "));
+}
+
+#[test]
+fn csv_round_trips_through_cbor() {
+    let csv = "t,temp\n0,21.5\n1,-4.0\n";
+    let out = cbor_ta(
+        &["from-csv", "-t", "t=u16", "-e", "be", "-"],
+        csv.as_bytes(),
+    );
+    assert!(out.status.success());
+    let listed = cbor_ta(&["-"], &out.stdout);
+    let stdout = String::from_utf8(listed.stdout).unwrap();
+    assert!(stdout.contains("#0.t: u16 be len=2"));
+    assert!(stdout.contains("#0.temp: f64 be len=2"));
+
+    let back = cbor_ta(&["to-csv", "--tsv", "-"], &out.stdout);
+    assert_eq!(
+        String::from_utf8(back.stdout).unwrap(),
+        "#0.t\t#0.temp\n0\t21.5\n1\t-4.0\n"
+    );
+}
+
+#[test]
+fn from_csv_reports_line_and_column() {
+    let out = cbor_ta(&["from-csv", "-t", "u8", "-"], b"a,b\n1,2\n3,300\n");
+    assert!(!out.status.success());
+    assert_eq!(
+        String::from_utf8(out.stderr).unwrap(),
+        "cbor-ta: -: line 3, column 2: \"300\" is not a valid u8\n"
+    );
+}
+
+#[test]
+fn csv_of_uneven_arrays_reads_back() {
+    let csv = cbor_ta(&["to-csv", "-"], &sample());
+    assert!(csv.status.success());
+    let out = cbor_ta(&["from-csv", "-"], &csv.stdout);
+    assert!(out.status.success());
+    let listed = String::from_utf8(cbor_ta(&["-"], &out.stdout).stdout).unwrap();
+    for len in ["len=20", "len=4", "len=0", "len=3"] {
+        assert!(listed.contains(len), "{len} missing from {listed}");
+    }
+}
@@ -69,6 +69,57 @@ fn roundtrip_f16() {
     use minicbor_typed_arrays::half::f16;
     roundtrip::<f16>(&[f16::from_f32(7.0), f16::from_f32(-8.5)]);
 }
+#[cfg(feature = "half")]
+#[test]
+fn roundtrip_bf16() {
+    use minicbor_typed_arrays::half::bf16;
+    roundtrip::<bf16>(&[bf16::from_f32(7.0), bf16::from_f32(-8.5)]);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn bf16_marker_wraps_u16_array() {
+    use minicbor_typed_arrays::half::bf16;
+    use minicbor_typed_arrays::{ElementReader, IoSource, BF16_MARKER_TAG};
+    let array = TypedArray::from_slice(&[bf16::from_f32(1.5)], Endianness::Little);
+    let mut buf = Vec::new();
+    minicbor::encode(&array, &mut buf).unwrap();
+
+    let mut d = minicbor::Decoder::new(&buf);
+    assert_eq!(d.tag().unwrap(), Tag::new(BF16_MARKER_TAG));
+    let raw: TypedArrayRef = d.decode().unwrap();
+    assert_eq!(raw.element_type(), ElementType::U16);
+    assert_eq!(raw.as_bytes(), array.as_bytes());
+
+    let mut reader = ElementReader::new(IoSource(&buf[..])).unwrap();
+    assert_eq!(reader.element_type(), ElementType::Bf16);
+    assert_eq!(
+        reader.next_element().unwrap(),
+        Some(Element::Bf16(bf16::from_f32(1.5)))
+    );
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn bf16_custom_tags_round_trip() {
+    use minicbor_typed_arrays::half::bf16;
+    use minicbor_typed_arrays::Bf16Encoding;
+    let tags = Bf16Encoding::Tags {
+        big: 65500,
+        little: 65501,
+    };
+    let array = TypedArray::from_slice(&[bf16::from_f32(-2.0)], Endianness::Big);
+    let mut buf = Vec::new();
+    array
+        .encode_with(&mut minicbor::Encoder::new(&mut buf), tags)
+        .unwrap();
+    assert_eq!(minicbor::Decoder::new(&buf).tag().unwrap(), Tag::new(65500));
+
+    let back = OwnedTypedArray::decode_with(&mut minicbor::Decoder::new(&buf), tags).unwrap();
+    assert_eq!(back, array);
+    // The default encoding does not know these tags.
+    assert!(minicbor::decode::<OwnedTypedArray>(&buf).is_err());
+}
 
 #[test]
 fn iter_values() {
@@ -0,0 +1,92 @@
+#![cfg(feature = "serde")]
+
+use minicbor_typed_arrays::{Element, ElementType, Endianness, OwnedTypedArray, TypedArray};
+
+#[test]
+fn ciborium_emits_rfc8746_tag() {
+    let array = TypedArray::from_slice(&[1.0f32, 2.0], Endianness::Little);
+    let mut via_serde = Vec::new();
+    ciborium::into_writer(&array, &mut via_serde).unwrap();
+    let mut via_minicbor = Vec::new();
+    minicbor::encode(&array, &mut via_minicbor).unwrap();
+    assert_eq!(via_serde, via_minicbor);
+
+    let back: OwnedTypedArray = ciborium::from_reader(&via_minicbor[..]).unwrap();
+    assert_eq!(back, array);
+}
+
+#[test]
+fn ciborium_round_trips_bf16_marker() {
+    use minicbor_typed_arrays::half::bf16;
+    let array = TypedArray::from_slice(&[bf16::from_f32(0.25)], Endianness::Big);
+    let mut via_serde = Vec::new();
+    ciborium::into_writer(&array, &mut via_serde).unwrap();
+    let mut via_minicbor = Vec::new();
+    minicbor::encode(&array, &mut via_minicbor).unwrap();
+    assert_eq!(via_serde, via_minicbor);
+
+    let back: OwnedTypedArray = ciborium::from_reader(&via_minicbor[..]).unwrap();
+    assert_eq!(back, array);
+}
+
+#[test]
+fn ciborium_rejects_untagged_bytes() {
+    let mut buf = Vec::new();
+    minicbor::Encoder::new(&mut buf).bytes(&[1, 2]).unwrap();
+    let r: Result<OwnedTypedArray, _> = ciborium::from_reader(&buf[..]);
+    assert!(r.is_err());
+}
+
+#[test]
+fn json_is_readable() {
+    let array = TypedArray::from_slice(&[-1i16, 2], Endianness::Big);
+    let json = serde_json::to_string(&array).unwrap();
+    assert_eq!(json, r#"{"type":"i16","endianness":"be","values":[-1,2]}"#);
+    let back: OwnedTypedArray = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, array);
+}
+
+#[test]
+fn json_accepts_any_field_order_and_checks_range() {
+    let back: OwnedTypedArray =
+        serde_json::from_str(r#"{"values":[1.5,2],"endianness":"le","type":"f64"}"#).unwrap();
+    assert_eq!(
+        back,
+        TypedArray::from_slice(&[1.5f64, 2.0], Endianness::Little)
+    );
+    let r: Result<OwnedTypedArray, _> =
+        serde_json::from_str(r#"{"type":"u8","endianness":"be","values":[256]}"#);
+    assert!(r.is_err());
+}
+
+#[test]
+fn json_rejects_values_that_do_not_fit() {
+    let r: Result<OwnedTypedArray, _> =
+        serde_json::from_str(r#"{"type":"u8clamped","endianness":"be","values":[300]}"#);
+    assert!(r
+        .unwrap_err()
+        .to_string()
+        .starts_with("value 0 is not a valid u8clamped"));
+    let r: Result<OwnedTypedArray, _> =
+        serde_json::from_str(r#"{"type":"i32","endianness":"be","values":[1,2.5]}"#);
+    assert!(r
+        .unwrap_err()
+        .to_string()
+        .starts_with("value 1 is not a valid i32"));
+}
+
+#[test]
+fn metadata_types_round_trip() {
+    assert_eq!(
+        serde_json::to_string(&ElementType::U8Clamped).unwrap(),
+        r#""u8clamped""#
+    );
+    assert_eq!(
+        serde_json::to_string(&Endianness::Little).unwrap(),
+        r#""le""#
+    );
+    let e: Element = serde_json::from_str(r#"{"u32":7}"#).unwrap();
+    assert_eq!(e, Element::U32(7));
+    let et: ElementType = serde_json::from_str(r#""f32""#).unwrap();
+    assert_eq!(et, ElementType::F32);
+}